@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use {DeviceId, LogicalPosition, LogicalSize, WindowId};
+use {DeviceId, LogicalPosition, LogicalSize, MonitorId, WindowId, WindowState};
 
 /// Describes a generic event.
 #[derive(Clone, Debug)]
@@ -15,6 +15,39 @@ pub enum Event {
     },
     Awakened,
 
+    /// The active keyboard layout has changed, e.g. because the user switched from a US to a Russian layout.
+    ///
+    /// Key labels derived from the previous layout should be refreshed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland. On Wayland, this requires libxkbcommon.
+    KeyboardLayoutChanged,
+
+    /// A monitor has been connected or enabled.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland.
+    MonitorAdded(MonitorId),
+
+    /// A monitor has been disconnected or disabled.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland. On Wayland, the properties of the monitor are no longer
+    /// available by the time this is received, so it should only be compared against monitors
+    /// received previously through their native identifier.
+    MonitorRemoved(MonitorId),
+
+    /// The configuration of a monitor has changed, e.g. its resolution, position, refresh rate,
+    /// transform or DPI factor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland.
+    MonitorChanged(MonitorId),
+
     /// The application has been suspended or resumed.
     ///
     /// The parameter is true if app was suspended, and false if it has been resumed.
@@ -53,8 +86,47 @@ pub enum WindowEvent {
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
     Focused(bool),
 
+    /// The window has been maximized, minimized, made fullscreen, focused, shown or hidden, or the
+    /// reverse. Contains the window's new state.
+    ///
+    /// This is also emitted when the window manager changes the state on its own.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland.
+    StateChanged(WindowState),
+
+    /// The window has been completely hidden from view, or has become at least partially visible
+    /// again.
+    ///
+    /// The parameter is true if the window is occluded, e.g. because it's covered by other
+    /// windows, minimized or hidden. Applications can use this to stop rendering while nothing
+    /// would be shown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland. Wayland doesn't report occlusion directly, so the window is
+    /// considered occluded when the compositor doesn't show a new frame within a second. This is
+    /// only checked for the frames drawn in response to `Resized` and `Refresh`, so occlusion can
+    /// go unnoticed until one of them is emitted.
+    Occluded(bool),
+
     /// An event from the keyboard has been received.
-    KeyboardInput { device_id: DeviceId, input: KeyboardInput },
+    KeyboardInput {
+        device_id: DeviceId,
+        input: KeyboardInput,
+        /// `true` if the event was generated by winit rather than by an actual key press or release, which happens
+        /// when the window's focus changes:
+        ///
+        /// * When the window gains focus, a press is generated for every key already held down.
+        /// * When the window loses focus, a release is generated for every key still held down, since the actual
+        ///   release will be delivered to another window.
+        ///
+        /// ## Platform-specific
+        ///
+        /// Synthetic events are only generated on X11 and Wayland. This is always `false` on other platforms.
+        is_synthetic: bool,
+    },
 
     /// The cursor has moved on the window.
     CursorMoved {
@@ -144,6 +216,13 @@ pub enum DeviceEvent {
     Button { button: ButtonId, state: ElementState },
     Key(KeyboardInput),
     Text { codepoint: char },
+
+    /// A global hotkey was pressed. Contains the ID returned when the hotkey was registered.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11, where hotkeys are registered with `EventsLoopExt::register_global_hotkey`.
+    HotkeyPressed(u32),
 }
 
 /// Describes a keyboard input event.
@@ -158,12 +237,27 @@ pub struct KeyboardInput {
 
     pub state: ElementState,
 
+    /// `true` if this press was generated by the key being held down, rather than by the key being pressed.
+    ///
+    /// Key repeat rate and delay are determined by the host's settings. This is always `false` for `Released` events.
+    pub is_repeat: bool,
+
     /// Identifies the semantic meaning of the key
     ///
     /// Use when the semantics of the key are more important than the physical location of the key, such as when
     /// implementing appropriate behavior for "page up."
     pub virtual_keycode: Option<VirtualKeyCode>,
 
+    /// Identifies the physical key pressed, independently of the keyboard layout
+    ///
+    /// This is a portable equivalent of `scancode`. Use it for bindings that depend on the key's position, such as
+    /// WASD movement, so that they keep working on AZERTY or Dvorak layouts.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only implemented on X11 and Wayland. This is always `None` on other platforms.
+    pub physical_key: Option<PhysicalKey>,
+
     /// Modifier keys active at the time of this input.
     ///
     /// This is tracked internally to avoid tracking errors arising from modifier key state changes when events from
@@ -445,6 +539,190 @@ pub enum VirtualKeyCode {
     Cut,
 }
 
+/// Layout-independent identifier for a physical key.
+///
+/// Variants are named after the key's position on a US QWERTY keyboard, following the W3C
+/// [`KeyboardEvent.code`](https://www.w3.org/TR/uievents-code/) values. For instance, `KeyW` is
+/// the key to the right of `KeyQ`, even on layouts where it's labelled `Z`.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum PhysicalKey {
+    /// <code>`</code> on a US keyboard.
+    Backquote,
+    /// `\` on a US keyboard. Labelled `#` on a UK keyboard.
+    Backslash,
+    /// `[` on a US keyboard.
+    BracketLeft,
+    /// `]` on a US keyboard.
+    BracketRight,
+    /// `,` on a US keyboard.
+    Comma,
+    /// `0` on a US keyboard.
+    Digit0,
+    /// `1` on a US keyboard.
+    Digit1,
+    /// `2` on a US keyboard.
+    Digit2,
+    /// `3` on a US keyboard.
+    Digit3,
+    /// `4` on a US keyboard.
+    Digit4,
+    /// `5` on a US keyboard.
+    Digit5,
+    /// `6` on a US keyboard.
+    Digit6,
+    /// `7` on a US keyboard.
+    Digit7,
+    /// `8` on a US keyboard.
+    Digit8,
+    /// `9` on a US keyboard.
+    Digit9,
+    /// `=` on a US keyboard.
+    Equal,
+    /// The additional key next to the left `Shift` on ISO keyboards. Labelled `\` on a UK keyboard.
+    IntlBackslash,
+    /// The additional key next to the right `Shift` on Japanese keyboards. Labelled `\` (ro).
+    IntlRo,
+    /// The additional key next to `Backspace` on Japanese keyboards. Labelled `¥`.
+    IntlYen,
+    KeyA,
+    KeyB,
+    KeyC,
+    KeyD,
+    KeyE,
+    KeyF,
+    KeyG,
+    KeyH,
+    KeyI,
+    KeyJ,
+    KeyK,
+    KeyL,
+    KeyM,
+    KeyN,
+    KeyO,
+    KeyP,
+    KeyQ,
+    KeyR,
+    KeyS,
+    KeyT,
+    KeyU,
+    KeyV,
+    KeyW,
+    KeyX,
+    KeyY,
+    KeyZ,
+    /// `-` on a US keyboard.
+    Minus,
+    /// `.` on a US keyboard.
+    Period,
+    /// `'` on a US keyboard.
+    Quote,
+    /// `;` on a US keyboard.
+    Semicolon,
+    /// `/` on a US keyboard.
+    Slash,
+
+    AltLeft,
+    /// Labelled `AltGr` on many keyboard layouts.
+    AltRight,
+    Backspace,
+    CapsLock,
+    /// The application context menu key, which is typically found between the right `Meta` key and the right
+    /// `Control` key.
+    ContextMenu,
+    ControlLeft,
+    ControlRight,
+    Enter,
+    /// The left "logo" key: the Windows key on PC and the Command key on Mac.
+    MetaLeft,
+    /// The right "logo" key: the Windows key on PC and the Command key on Mac.
+    MetaRight,
+    ShiftLeft,
+    ShiftRight,
+    Space,
+    Tab,
+    /// Japanese: 変換 (henkan)
+    Convert,
+    /// Japanese: カタカナ/ひらがな/ローマ字 (katakana/hiragana/romaji)
+    KanaMode,
+    /// Korean: HangulMode 한/영 (han/yeong)
+    Lang1,
+    /// Korean: Hanja 한자 (hanja)
+    Lang2,
+    /// Japanese: 無変換 (muhenkan)
+    NonConvert,
+
+    Delete,
+    End,
+    Home,
+    Insert,
+    PageDown,
+    PageUp,
+
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+
+    NumLock,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    /// The numpad `,` key found on some international keyboards.
+    NumpadComma,
+    NumpadDecimal,
+    NumpadDivide,
+    NumpadEnter,
+    NumpadEqual,
+    NumpadMultiply,
+    NumpadSubtract,
+
+    Escape,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+
+    AudioVolumeDown,
+    AudioVolumeMute,
+    AudioVolumeUp,
+    MediaPlayPause,
+    MediaStop,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    Power,
+}
+
 /// Represents the current state of the keyboard modifiers
 ///
 /// Each field of this struct represents a modifier and is `true` if this modifier is active.
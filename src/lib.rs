@@ -115,7 +115,19 @@ extern crate smithay_client_toolkit as sctk;
 
 pub(crate) use dpi::*; // TODO: Actually change the imports throughout the codebase.
 pub use events::*;
-pub use window::{AvailableMonitorsIter, MonitorId};
+pub use window::{
+    AvailableMonitorsIter,
+    GammaRamp,
+    HitTestResult,
+    MonitorId,
+    MonitorTransform,
+    Rect,
+    ResizeDirection,
+    SubpixelLayout,
+    UserAttentionType,
+    VideoMode,
+    WindowState,
+};
 pub use icon::*;
 
 pub mod dpi;
@@ -224,6 +236,51 @@ impl EventsLoop {
         MonitorId { inner: self.events_loop.get_primary_monitor() }
     }
 
+    /// Returns `true` if `key` is currently held down.
+    ///
+    /// Unlike `KeyboardInput` events, this also accounts for keys pressed while none of the application's windows
+    /// had focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 and Windows:** Reflects the state of the whole keyboard.
+    /// - **Wayland:** Only keys pressed while a window had focus, or held when it gained focus, are known.
+    /// - **macOS, iOS, Android and Emscripten:** Unsupported; always returns `false`.
+    #[inline]
+    pub fn is_key_pressed(&self, key: VirtualKeyCode) -> bool {
+        self.get_pressed_keys().contains(&key)
+    }
+
+    /// Returns the keys currently held down.
+    ///
+    /// See `is_key_pressed` for the platform-specific caveats.
+    #[inline]
+    pub fn get_pressed_keys(&self) -> Vec<VirtualKeyCode> {
+        self.events_loop.get_pressed_keys()
+    }
+
+    /// Returns `true` if `button` is currently held down.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 and Windows:** Reflects the state of the whole mouse.
+    /// - **Wayland:** Only buttons pressed while the cursor is over one of the application's windows are known.
+    /// - **macOS, iOS, Android and Emscripten:** Unsupported; always returns `false`.
+    #[inline]
+    pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.events_loop.get_pressed_mouse_buttons().contains(&button)
+    }
+
+    /// Returns the position of the cursor in physical pixels, relative to the top-left corner of the desktop.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only implemented on X11 and Windows. Returns `None` on other platforms.
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<PhysicalPosition> {
+        self.events_loop.get_cursor_position()
+    }
+
     /// Fetches all the events that are pending, calls the callback function for each of them,
     /// and returns.
     #[inline]
@@ -431,6 +488,11 @@ pub struct WindowAttributes {
     /// The default is `false`.
     pub maximized: bool,
 
+    /// Whether the window should be minimized upon creation.
+    ///
+    /// The default is `false`.
+    pub minimized: bool,
+
     /// Whether the window should be immediately visible upon creation.
     ///
     /// The default is `true`.
@@ -452,6 +514,11 @@ pub struct WindowAttributes {
     /// The default is `false`.
     pub always_on_top: bool,
 
+    /// The opacity of the whole window, including its decorations, from `0.0` to `1.0`.
+    ///
+    /// The default is `1.0`.
+    pub opacity: f32,
+
     /// The window icon.
     ///
     /// The default is `None`.
@@ -472,11 +539,13 @@ impl Default for WindowAttributes {
             resizable: true,
             title: "winit window".to_owned(),
             maximized: false,
+            minimized: false,
             fullscreen: None,
             visible: true,
             transparent: false,
             decorations: true,
             always_on_top: false,
+            opacity: 1.0,
             window_icon: None,
             multitouch: false,
         }
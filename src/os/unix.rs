@@ -7,7 +7,10 @@ use std::sync::Arc;
 use {
     EventsLoop,
     LogicalSize,
+    ModifiersState,
     MonitorId,
+    ScanCode,
+    VirtualKeyCode,
     Window,
     WindowBuilder,
 };
@@ -41,6 +44,38 @@ pub trait EventsLoopExt {
     /// True if the `EventsLoop` uses X11.
     fn is_x11(&self) -> bool;
 
+    /// Returns the name of the active keyboard layout, e.g. "English (US)".
+    ///
+    /// `Event::KeyboardLayoutChanged` is emitted whenever this changes.
+    ///
+    /// Returns `None` if the name isn't known, which is always the case on Wayland if libxkbcommon isn't available.
+    fn get_keyboard_layout(&self) -> Option<String>;
+
+    /// Returns the text that the key identified by `scancode` produces in the active keyboard layout, when no
+    /// modifiers are held.
+    ///
+    /// For instance, the key labelled `Z` on a US keyboard produces `"y"` with a German layout. This is what
+    /// should be shown to users when displaying bindings based on `KeyboardInput::scancode` or
+    /// `KeyboardInput::physical_key`.
+    ///
+    /// Returns `None` for keys that don't produce printable text, such as `Shift` or `Enter`, and on Wayland if
+    /// libxkbcommon isn't available.
+    fn get_key_label(&self, scancode: ScanCode) -> Option<String>;
+
+    /// Registers a keyboard shortcut that works even while another application has focus.
+    ///
+    /// Whenever `key` is pressed with exactly `modifiers` held, a `DeviceEvent::HotkeyPressed` containing the
+    /// returned ID is emitted. The state of Caps Lock and Num Lock is ignored.
+    ///
+    /// Returns `None` if the shortcut is already taken by another application, if `key` isn't on the keyboard, or
+    /// on Wayland, where global hotkeys are unsupported.
+    fn register_global_hotkey(&self, modifiers: ModifiersState, key: VirtualKeyCode) -> Option<u32>;
+
+    /// Releases a shortcut registered with `register_global_hotkey`. Does nothing if `id` isn't registered.
+    ///
+    /// Returns an error if the X server refused to release the key grab.
+    fn unregister_global_hotkey(&self, id: u32) -> Result<(), String>;
+
     #[doc(hidden)]
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>>;
 }
@@ -77,6 +112,26 @@ impl EventsLoopExt for EventsLoop {
         !self.events_loop.is_wayland()
     }
 
+    #[inline]
+    fn get_keyboard_layout(&self) -> Option<String> {
+        self.events_loop.get_keyboard_layout()
+    }
+
+    #[inline]
+    fn get_key_label(&self, scancode: ScanCode) -> Option<String> {
+        self.events_loop.get_key_label(scancode)
+    }
+
+    #[inline]
+    fn register_global_hotkey(&self, modifiers: ModifiersState, key: VirtualKeyCode) -> Option<u32> {
+        self.events_loop.register_global_hotkey(modifiers, key)
+    }
+
+    #[inline]
+    fn unregister_global_hotkey(&self, id: u32) -> Result<(), String> {
+        self.events_loop.unregister_global_hotkey(id)
+    }
+
     #[inline]
     #[doc(hidden)]
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>> {
@@ -104,6 +159,7 @@ pub trait WindowExt {
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>>;
 
     /// Set window urgency hint (`XUrgencyHint`). Only relevant on X.
+    #[deprecated(note = "use `Window::request_user_attention` instead")]
     fn set_urgent(&self, is_urgent: bool);
 
     /// This function returns the underlying `xcb_connection_t` of an xlib `Display`.
@@ -72,11 +72,19 @@ use objc::runtime::{BOOL, Class, Object, Sel, YES};
 use {
     CreationError,
     Event,
+    GammaRamp,
+    HitTestResult,
     LogicalPosition,
     LogicalSize,
+    MonitorTransform,
     MouseCursor,
     PhysicalPosition,
     PhysicalSize,
+    Rect,
+    ResizeDirection,
+    SubpixelLayout,
+    UserAttentionType,
+    VideoMode,
     WindowAttributes,
     WindowEvent,
     WindowId as RootEventId,
@@ -197,6 +205,66 @@ impl MonitorId {
         let scale: CGFloat = unsafe { msg_send![self.get_uiscreen(), nativeScale] };
         scale as f64
     }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial_number(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_persistent_id(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> (PhysicalPosition, PhysicalSize) {
+        (self.get_position(), self.get_dimensions())
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &GammaRamp) -> Result<(), String> {
+        Err("Changing the gamma ramp is not yet possible on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline]
+    pub fn get_physical_dimensions(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_layout(&self) -> SubpixelLayout {
+        SubpixelLayout::Unknown
+    }
 }
 
 pub struct EventsLoop {
@@ -228,6 +296,18 @@ impl EventsLoop {
         MonitorId
     }
 
+    pub fn get_pressed_keys(&self) -> Vec<::VirtualKeyCode> {
+        Vec::new()
+    }
+
+    pub fn get_pressed_mouse_buttons(&self) -> Vec<::MouseButton> {
+        Vec::new()
+    }
+
+    pub fn get_cursor_position(&self) -> Option<::PhysicalPosition> {
+        None
+    }
+
     pub fn poll_events<F>(&mut self, mut callback: F)
         where F: FnMut(::Event)
     {
@@ -383,6 +463,11 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn focus(&self) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_position(&self) -> Option<LogicalPosition> {
         // N/A
@@ -440,6 +525,46 @@ impl Window {
         Err("Cursor grabbing is not possible on iOS.".to_owned())
     }
 
+    #[inline]
+    pub fn grab_keyboard(&self, _grab: bool) -> Result<(), String> {
+        Err("Keyboard grabbing is not possible on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), String> {
+        Err("Moving the window is not possible on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), String> {
+        Err("Resizing the window is not possible on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_hit_test(&self, _hit_test: Option<Box<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: LogicalPosition) -> Result<(), String> {
+        Err("Showing the window menu is not possible on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_input_region(&self, _region: Option<Vec<Rect>>) -> Result<(), String> {
+        Err("Changing the input region is not possible on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_fullscreen_exclusive(&self, _monitor: RootMonitorId, _video_mode: VideoMode) -> Result<(), String> {
+        Err("Changing the video mode is not yet possible on iOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_fullscreen_monitors(&self, _monitors: &[RootMonitorId]) -> Result<(), String> {
+        Err("Spanning fullscreen across monitors is not yet possible on iOS.".to_owned())
+    }
+
     #[inline]
     pub fn hide_cursor(&self, _hide: bool) {
         // N/A
@@ -455,12 +580,42 @@ impl Window {
         Err("Setting cursor position is not possible on iOS.".to_owned())
     }
 
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn set_maximized(&self, _maximized: bool) {
         // N/A
         // iOS has single screen maximized apps so nothing to do
     }
 
+    #[inline]
+    pub fn set_minimized(&self, _minimized: bool) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_fullscreen(&self, _monitor: Option<RootMonitorId>) {
         // N/A
@@ -477,6 +632,16 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_window_icon(&self, _icon: Option<::Icon>) {
         // N/A
@@ -12,9 +12,20 @@ use sctk::reexports::client::ConnectError;
 use {
     CreationError,
     EventsLoopClosed,
+    GammaRamp,
+    HitTestResult,
     Icon,
+    ModifiersState,
+    MonitorTransform,
+    MouseButton,
     MouseCursor,
     ControlFlow,
+    Rect,
+    ResizeDirection,
+    SubpixelLayout,
+    UserAttentionType,
+    VideoMode,
+    VirtualKeyCode,
     WindowAttributes,
 };
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
@@ -24,8 +35,10 @@ use self::x11::ffi::XVisualInfo;
 pub use self::x11::XNotSupported;
 
 mod dlopen;
+mod evdev;
 pub mod wayland;
 pub mod x11;
+mod xkbcommon;
 
 /// Environment variable specifying which backend should be used on unix platform.
 ///
@@ -117,6 +130,102 @@ impl MonitorId {
             &MonitorId::Wayland(ref m) => m.get_hidpi_factor() as f64,
         }
     }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        match self {
+            &MonitorId::X(ref m) => m.get_refresh_rate(),
+            &MonitorId::Wayland(ref m) => m.get_refresh_rate(),
+        }
+    }
+
+    #[inline]
+    pub fn get_physical_dimensions(&self) -> Option<(u32, u32)> {
+        match self {
+            &MonitorId::X(ref m) => m.get_physical_dimensions(),
+            &MonitorId::Wayland(ref m) => m.get_physical_dimensions(),
+        }
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        match self {
+            &MonitorId::X(ref m) => m.get_transform(),
+            &MonitorId::Wayland(ref m) => m.get_transform(),
+        }
+    }
+
+    #[inline]
+    pub fn get_subpixel_layout(&self) -> SubpixelLayout {
+        match self {
+            &MonitorId::X(ref m) => m.get_subpixel_layout(),
+            &MonitorId::Wayland(ref m) => m.get_subpixel_layout(),
+        }
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_manufacturer(),
+            &MonitorId::Wayland(ref m) => m.get_manufacturer(),
+        }
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_model(),
+            &MonitorId::Wayland(ref m) => m.get_model(),
+        }
+    }
+
+    #[inline]
+    pub fn get_serial_number(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_serial_number(),
+            &MonitorId::Wayland(ref m) => m.get_serial_number(),
+        }
+    }
+
+    #[inline]
+    pub fn get_persistent_id(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_persistent_id(),
+            &MonitorId::Wayland(ref m) => m.get_persistent_id(),
+        }
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> (PhysicalPosition, PhysicalSize) {
+        match self {
+            &MonitorId::X(ref m) => m.get_work_area(),
+            &MonitorId::Wayland(ref m) => m.get_work_area(),
+        }
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        match self {
+            &MonitorId::X(ref m) => m.get_gamma_ramp(),
+            &MonitorId::Wayland(ref m) => m.get_gamma_ramp(),
+        }
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), String> {
+        match self {
+            &MonitorId::X(ref m) => m.set_gamma_ramp(ramp),
+            &MonitorId::Wayland(ref m) => m.set_gamma_ramp(ramp),
+        }
+    }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        match self {
+            &MonitorId::X(ref m) => m.get_video_modes(),
+            &MonitorId::Wayland(ref m) => m.get_video_modes(),
+        }
+    }
 }
 
 impl Window {
@@ -168,6 +277,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn focus(&self) {
+        match self {
+            &Window::X(ref w) => w.focus(),
+            &Window::Wayland(ref w) => w.focus(),
+        }
+    }
+
     #[inline]
     pub fn get_position(&self) -> Option<LogicalPosition> {
         match self {
@@ -256,6 +373,54 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn grab_keyboard(&self, grab: bool) -> Result<(), String> {
+        match self {
+            &Window::X(ref window) => window.grab_keyboard(grab),
+            &Window::Wayland(ref window) => window.grab_keyboard(grab),
+        }
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.drag_window(),
+            &Window::Wayland(ref w) => w.drag_window(),
+        }
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.drag_resize_window(direction),
+            &Window::Wayland(ref w) => w.drag_resize_window(direction),
+        }
+    }
+
+    #[inline]
+    pub fn set_hit_test(&self, hit_test: Option<Box<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>) {
+        match self {
+            &Window::X(ref w) => w.set_hit_test(hit_test),
+            &Window::Wayland(ref w) => w.set_hit_test(hit_test),
+        }
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, position: LogicalPosition) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.show_window_menu(position),
+            &Window::Wayland(ref w) => w.show_window_menu(position),
+        }
+    }
+
+    #[inline]
+    pub fn set_input_region(&self, region: Option<Vec<Rect>>) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.set_input_region(region),
+            &Window::Wayland(ref w) => w.set_input_region(region),
+        }
+    }
+
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
         match self {
@@ -280,6 +445,46 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        match self {
+            &Window::X(ref w) => w.is_maximized(),
+            &Window::Wayland(ref w) => w.is_maximized(),
+        }
+    }
+
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        match self {
+            &Window::X(ref w) => w.is_minimized(),
+            &Window::Wayland(ref w) => w.is_minimized(),
+        }
+    }
+
+    #[inline]
+    pub fn is_fullscreen(&self) -> bool {
+        match self {
+            &Window::X(ref w) => w.is_fullscreen(),
+            &Window::Wayland(ref w) => w.is_fullscreen(),
+        }
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        match self {
+            &Window::X(ref w) => w.is_focused(),
+            &Window::Wayland(ref w) => w.is_focused(),
+        }
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        match self {
+            &Window::X(ref w) => w.is_visible(),
+            &Window::Wayland(ref w) => w.is_visible(),
+        }
+    }
+
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         match self {
@@ -288,6 +493,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        match self {
+            &Window::X(ref w) => w.set_minimized(minimized),
+            &Window::Wayland(ref w) => w.set_minimized(minimized),
+        }
+    }
+
     #[inline]
     pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
         match self {
@@ -296,6 +509,22 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_fullscreen_exclusive(&self, monitor: RootMonitorId, video_mode: VideoMode) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.set_fullscreen_exclusive(monitor, video_mode),
+            &Window::Wayland(ref w) => w.set_fullscreen_exclusive(monitor, video_mode),
+        }
+    }
+
+    #[inline]
+    pub fn set_fullscreen_monitors(&self, monitors: &[RootMonitorId]) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.set_fullscreen_monitors(monitors),
+            &Window::Wayland(ref w) => w.set_fullscreen_monitors(monitors),
+        }
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         match self {
@@ -312,6 +541,22 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        match self {
+            &Window::X(ref w) => w.set_opacity(opacity),
+            &Window::Wayland(ref w) => w.set_opacity(opacity),
+        }
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        match self {
+            &Window::X(ref w) => w.request_user_attention(request_type),
+            &Window::Wayland(ref w) => w.request_user_attention(request_type),
+        }
+    }
+
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         match self {
@@ -520,6 +765,63 @@ r#"Failed to initialize any backend!
             EventsLoop::X(ref ev) => Some(ev.x_connection()),
         }
     }
+
+    #[inline]
+    pub fn get_keyboard_layout(&self) -> Option<String> {
+        match *self {
+            EventsLoop::Wayland(ref evlp) => evlp.get_keyboard_layout(),
+            EventsLoop::X(ref evlp) => evlp.x_connection().get_keyboard_layout(),
+        }
+    }
+
+    #[inline]
+    pub fn get_key_label(&self, scancode: u32) -> Option<String> {
+        match *self {
+            EventsLoop::Wayland(ref evlp) => evlp.get_key_label(scancode),
+            EventsLoop::X(ref evlp) => evlp.x_connection().get_key_label(scancode),
+        }
+    }
+
+    #[inline]
+    pub fn register_global_hotkey(&self, modifiers: ModifiersState, key: VirtualKeyCode) -> Option<u32> {
+        match *self {
+            EventsLoop::Wayland(_) => None,
+            EventsLoop::X(ref evlp) => evlp.register_global_hotkey(modifiers, key),
+        }
+    }
+
+    #[inline]
+    pub fn unregister_global_hotkey(&self, id: u32) -> Result<(), String> {
+        match *self {
+            EventsLoop::Wayland(_) => Ok(()),
+            EventsLoop::X(ref evlp) => evlp.unregister_global_hotkey(id),
+        }
+    }
+
+    #[inline]
+    pub fn get_pressed_keys(&self) -> Vec<VirtualKeyCode> {
+        match *self {
+            EventsLoop::Wayland(ref evlp) => evlp.get_pressed_keys(),
+            EventsLoop::X(ref evlp) => evlp.get_pressed_keys(),
+        }
+    }
+
+    #[inline]
+    pub fn get_pressed_mouse_buttons(&self) -> Vec<MouseButton> {
+        match *self {
+            EventsLoop::Wayland(ref evlp) => evlp.get_pressed_mouse_buttons(),
+            EventsLoop::X(ref evlp) => evlp.get_pressed_mouse_buttons(),
+        }
+    }
+
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<PhysicalPosition> {
+        match *self {
+            // Wayland clients aren't told where the cursor is outside of their own surfaces.
+            EventsLoop::Wayland(_) => None,
+            EventsLoop::X(ref evlp) => evlp.get_cursor_position(),
+        }
+    }
 }
 
 impl EventsLoopProxy {
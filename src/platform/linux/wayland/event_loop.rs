@@ -1,11 +1,25 @@
 use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex, Weak};
-
-use {ControlFlow, EventsLoopClosed, PhysicalPosition, PhysicalSize};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread;
+use std::time::Instant;
+
+use {
+    ControlFlow,
+    EventsLoopClosed,
+    GammaRamp,
+    MonitorTransform,
+    MouseButton,
+    PhysicalPosition,
+    PhysicalSize,
+    SubpixelLayout,
+    VideoMode,
+    VirtualKeyCode,
+};
 
+use platform::platform::xkbcommon::Keymap;
 use super::window::WindowStore;
 use super::WindowId;
 
@@ -15,6 +29,7 @@ use sctk::reexports::client::protocol::{
     wl_keyboard, wl_output, wl_pointer, wl_registry, wl_seat, wl_touch,
 };
 use sctk::reexports::client::{ConnectError, Display, EventQueue, GlobalEvent, Proxy};
+use sctk::reexports::protocols::unstable::keyboard_shortcuts_inhibit::v1::client::zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1;
 use sctk::Environment;
 
 use sctk::reexports::client::protocol::wl_display::RequestsTrait as DisplayRequests;
@@ -53,6 +68,14 @@ impl EventsLoopSink {
     }
 }
 
+// The keys and mouse buttons currently held down. Wayland doesn't let us query this, so it is
+// tracked from the input events directed at our windows.
+#[derive(Default)]
+pub struct InputState {
+    pub keys: HashSet<VirtualKeyCode>,
+    pub mouse_buttons: HashSet<MouseButton>,
+}
+
 pub struct EventsLoop {
     // The Event Queue
     pub evq: RefCell<EventQueue>,
@@ -70,6 +93,16 @@ pub struct EventsLoop {
     pub display: Arc<Display>,
     // The list of seats
     pub seats: Arc<Mutex<Vec<(u32, Proxy<wl_seat::WlSeat>)>>>,
+    // The keyboard shortcuts inhibitor manager, if the compositor supports it
+    pub shortcuts_inhibit_manager: Option<Proxy<ZwpKeyboardShortcutsInhibitManagerV1>>,
+    // The keymap of the most recently configured keyboard
+    keymap: Arc<Mutex<Option<Keymap>>>,
+    // The keys and mouse buttons currently held down
+    input_state: Arc<Mutex<InputState>>,
+    // The outputs as of the last dispatch, to tell which ones were added, removed or changed
+    monitors: Vec<(u32, Proxy<wl_output::WlOutput>, OutputState)>,
+    // Wakes us up when a frame callback times out
+    occlusion_timer: WakeupTimer,
 }
 
 // A handle that can be sent across threads and used to wake up the `EventsLoop`.
@@ -111,6 +144,8 @@ impl EventsLoop {
         let sink = Arc::new(Mutex::new(EventsLoopSink::new()));
         let store = Arc::new(Mutex::new(WindowStore::new()));
         let seats = Arc::new(Mutex::new(Vec::new()));
+        let keymap = Arc::new(Mutex::new(None));
+        let input_state = Arc::new(Mutex::new(InputState::default()));
 
         let env = Environment::from_registry_with_cb(
             display.get_registry().unwrap(),
@@ -119,6 +154,8 @@ impl EventsLoop {
                 sink: sink.clone(),
                 store: store.clone(),
                 seats: seats.clone(),
+                keymap: keymap.clone(),
+                input_state: input_state.clone(),
                 events_loop_proxy: EventsLoopProxy {
                     display: Arc::downgrade(&display),
                     pending_wakeup: Arc::downgrade(&pending_wakeup),
@@ -126,6 +163,13 @@ impl EventsLoop {
             },
         ).unwrap();
 
+        let monitors = output_states(&env.outputs);
+
+        let shortcuts_inhibit_manager = env.manager
+            .instantiate_auto::<ZwpKeyboardShortcutsInhibitManagerV1>()
+            .ok()
+            .map(|manager| manager.implement(|_, _| {}));
+
         Ok(EventsLoop {
             display,
             evq: RefCell::new(event_queue),
@@ -135,6 +179,11 @@ impl EventsLoop {
             env,
             cleanup_needed: Arc::new(Mutex::new(false)),
             seats,
+            shortcuts_inhibit_manager,
+            keymap,
+            input_state,
+            monitors,
+            occlusion_timer: WakeupTimer::new(Arc::downgrade(&display)),
         })
     }
 
@@ -213,6 +262,30 @@ impl EventsLoop {
     pub fn get_available_monitors(&self) -> VecDeque<MonitorId> {
         get_available_monitors(&self.env.outputs)
     }
+
+    pub fn get_keyboard_layout(&self) -> Option<String> {
+        self.keymap
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|keymap| keymap.layout_name())
+    }
+
+    pub fn get_key_label(&self, scancode: u32) -> Option<String> {
+        self.keymap
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|keymap| keymap.key_label(scancode))
+    }
+
+    pub fn get_pressed_keys(&self) -> Vec<VirtualKeyCode> {
+        self.input_state.lock().unwrap().keys.iter().cloned().collect()
+    }
+
+    pub fn get_pressed_mouse_buttons(&self) -> Vec<MouseButton> {
+        self.input_state.lock().unwrap().mouse_buttons.iter().cloned().collect()
+    }
 }
 
 /*
@@ -238,9 +311,28 @@ impl EventsLoop {
                 }
             }
         }
+        // report connected, disconnected and reconfigured outputs
+        {
+            let new_monitors = output_states(&self.env.outputs);
+            for &(id, ref proxy, ref state) in &new_monitors {
+                match self.monitors.iter().find(|&&(prev_id, _, _)| prev_id == id) {
+                    None => sink.send_raw_event(::Event::MonitorAdded(self.make_monitor_id(proxy))),
+                    Some(&(_, _, ref prev_state)) if prev_state != state => {
+                        sink.send_raw_event(::Event::MonitorChanged(self.make_monitor_id(proxy)))
+                    },
+                    _ => (),
+                }
+            }
+            for &(id, ref proxy, _) in &self.monitors {
+                if !new_monitors.iter().any(|&(new_id, _, _)| new_id == id) {
+                    sink.send_raw_event(::Event::MonitorRemoved(self.make_monitor_id(proxy)));
+                }
+            }
+            self.monitors = new_monitors;
+        }
         // process pending resize/refresh
         self.store.lock().unwrap().for_each(
-            |newsize, size, new_dpi, refresh, frame_refresh, closed, wid, frame| {
+            |newsize, size, new_dpi, new_state, occluded, refresh, frame_refresh, closed, wid, frame| {
                 if let Some(frame) = frame {
                     if let Some((w, h)) = newsize {
                         frame.resize(w, h);
@@ -258,6 +350,12 @@ impl EventsLoop {
                 if let Some(dpi) = new_dpi {
                     sink.send_event(::WindowEvent::HiDpiFactorChanged(dpi as f64), wid);
                 }
+                if let Some(state) = new_state {
+                    sink.send_event(::WindowEvent::StateChanged(state), wid);
+                }
+                if let Some(occluded) = occluded {
+                    sink.send_event(::WindowEvent::Occluded(occluded), wid);
+                }
                 if refresh {
                     sink.send_event(::WindowEvent::Refresh, wid);
                 }
@@ -265,10 +363,131 @@ impl EventsLoop {
                     sink.send_event(::WindowEvent::CloseRequested, wid);
                 }
             },
-        )
+        );
+        // nothing else may wake us up when a window becomes occluded
+        if let Some(deadline) = self.store.lock().unwrap().next_occlusion_deadline() {
+            self.occlusion_timer.schedule(deadline);
+        }
+    }
+}
+
+// Wakes up the `EventsLoop` at a given time, without emitting `Awakened`, so that the triggers
+// that depend on time run even if no other event arrives.
+struct WakeupTimer {
+    state: Arc<(Mutex<WakeupTimerState>, Condvar)>,
+}
+
+struct WakeupTimerState {
+    deadline: Option<Instant>,
+    shutdown: bool,
+}
+
+impl WakeupTimer {
+    fn new(display: Weak<Display>) -> WakeupTimer {
+        let state = Arc::new((
+            Mutex::new(WakeupTimerState {
+                deadline: None,
+                shutdown: false,
+            }),
+            Condvar::new(),
+        ));
+        let thread_state = state.clone();
+        thread::spawn(move || run_wakeup_timer(&thread_state, display));
+        WakeupTimer { state }
+    }
+
+    fn schedule(&self, deadline: Instant) {
+        let mut state = self.state.0.lock().unwrap();
+        if state.deadline.map_or(true, |current| deadline < current) {
+            state.deadline = Some(deadline);
+            self.state.1.notify_one();
+        }
+    }
+}
+
+impl Drop for WakeupTimer {
+    fn drop(&mut self) {
+        self.state.0.lock().unwrap().shutdown = true;
+        self.state.1.notify_one();
+    }
+}
+
+fn run_wakeup_timer(state: &(Mutex<WakeupTimerState>, Condvar), display: Weak<Display>) {
+    let (ref lock, ref condvar) = *state;
+    let mut timer = lock.lock().unwrap();
+    loop {
+        if timer.shutdown {
+            return;
+        }
+        let deadline = match timer.deadline {
+            Some(deadline) => deadline,
+            None => {
+                timer = condvar.wait(timer).unwrap();
+                continue;
+            },
+        };
+        let now = Instant::now();
+        if now < deadline {
+            timer = condvar.wait_timeout(timer, deadline - now).unwrap().0;
+            continue;
+        }
+        timer.deadline = None;
+        drop(timer);
+        match display.upgrade() {
+            // The reply to the sync request makes `dispatch` return
+            Some(display) => {
+                let _ = display.sync();
+                let _ = display.flush();
+            },
+            None => return,
+        }
+        timer = lock.lock().unwrap();
     }
 }
 
+impl EventsLoop {
+    fn make_monitor_id(&self, proxy: &Proxy<wl_output::WlOutput>) -> ::MonitorId {
+        ::MonitorId {
+            inner: ::platform::MonitorId::Wayland(MonitorId {
+                proxy: proxy.clone(),
+                mgr: self.env.outputs.clone(),
+            }),
+        }
+    }
+}
+
+// The properties of an output whose changes are reported through `Event::MonitorChanged`.
+#[derive(PartialEq)]
+struct OutputState {
+    location: (i32, i32),
+    // The dimensions and refresh rate of the current mode.
+    mode: Option<((i32, i32), i32)>,
+    scale_factor: i32,
+    transform: wl_output::Transform,
+}
+
+// Outputs are only reported once the compositor has sent their modes, so that their properties
+// are available by the time we emit `Event::MonitorAdded`.
+fn output_states(outputs: &OutputMgr) -> Vec<(u32, Proxy<wl_output::WlOutput>, OutputState)> {
+    outputs.with_all(|list| {
+        list.iter()
+            .filter(|&&(_, _, ref info)| !info.modes.is_empty())
+            .map(|&(id, ref proxy, ref info)| {
+                let state = OutputState {
+                    location: info.location,
+                    mode: info.modes
+                        .iter()
+                        .find(|m| m.is_current)
+                        .map(|m| (m.dimensions, m.refresh_rate)),
+                    scale_factor: info.scale_factor,
+                    transform: info.transform,
+                };
+                (id, proxy.clone(), state)
+            })
+            .collect()
+    })
+}
+
 /*
  * Wayland protocol implementations
  */
@@ -277,6 +496,8 @@ struct SeatManager {
     sink: Arc<Mutex<EventsLoopSink>>,
     store: Arc<Mutex<WindowStore>>,
     seats: Arc<Mutex<Vec<(u32, Proxy<wl_seat::WlSeat>)>>>,
+    keymap: Arc<Mutex<Option<Keymap>>>,
+    input_state: Arc<Mutex<InputState>>,
     events_loop_proxy: EventsLoopProxy,
 }
 
@@ -300,7 +521,10 @@ impl Implementation<Proxy<wl_registry::WlRegistry>, GlobalEvent> for SeatManager
                         store: self.store.clone(),
                         pointer: None,
                         keyboard: None,
+                        layout_tracker: None,
                         touch: None,
+                        keymap: self.keymap.clone(),
+                        input_state: self.input_state.clone(),
                         events_loop_proxy: self.events_loop_proxy.clone(),
                     });
                 self.store.lock().unwrap().new_seat(&seat);
@@ -325,7 +549,10 @@ struct SeatData {
     store: Arc<Mutex<WindowStore>>,
     pointer: Option<Proxy<wl_pointer::WlPointer>>,
     keyboard: Option<Proxy<wl_keyboard::WlKeyboard>>,
+    layout_tracker: Option<Proxy<wl_keyboard::WlKeyboard>>,
     touch: Option<Proxy<wl_touch::WlTouch>>,
+    keymap: Arc<Mutex<Option<Keymap>>>,
+    input_state: Arc<Mutex<InputState>>,
     events_loop_proxy: EventsLoopProxy,
 }
 
@@ -341,6 +568,7 @@ impl Implementation<Proxy<wl_seat::WlSeat>, wl_seat::Event> for SeatData {
                         seat.get_pointer().unwrap(),
                         self.sink.clone(),
                         self.store.clone(),
+                        self.input_state.clone(),
                     ))
                 }
                 // destroy pointer if applicable
@@ -357,12 +585,18 @@ impl Implementation<Proxy<wl_seat::WlSeat>, wl_seat::Event> for SeatData {
                     self.keyboard = Some(super::keyboard::init_keyboard(
                         seat.get_keyboard().unwrap(),
                         self.sink.clone(),
+                        self.input_state.clone(),
                         self.events_loop_proxy.clone(),
+                    ));
+                    self.layout_tracker = Some(super::keyboard::init_layout_tracker(
+                        seat.get_keyboard().unwrap(),
+                        self.sink.clone(),
+                        self.keymap.clone(),
                     ))
                 }
                 // destroy keyboard if applicable
                 if !capabilities.contains(wl_seat::Capability::Keyboard) {
-                    if let Some(kbd) = self.keyboard.take() {
+                    for kbd in self.keyboard.take().into_iter().chain(self.layout_tracker.take()) {
                         if kbd.version() >= 3 {
                             use self::wl_keyboard::RequestsTrait;
                             kbd.release();
@@ -399,7 +633,7 @@ impl Drop for SeatData {
                 pointer.release();
             }
         }
-        if let Some(kbd) = self.keyboard.take() {
+        for kbd in self.keyboard.take().into_iter().chain(self.layout_tracker.take()) {
             if kbd.version() >= 3 {
                 use self::wl_keyboard::RequestsTrait;
                 kbd.release();
@@ -492,6 +726,108 @@ impl MonitorId {
             .with_info(&self.proxy, |_, info| info.scale_factor)
             .unwrap_or(1)
     }
+
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        self.mgr
+            .with_info(&self.proxy, |_, info| {
+                info.modes
+                    .iter()
+                    .find(|m| m.is_current)
+                    // `wl_output` reports refresh rates in mHz.
+                    .map(|m| ((m.refresh_rate as f64) / 1000.0).round() as u16)
+            })
+            .and_then(|refresh_rate| refresh_rate)
+            .filter(|&refresh_rate| refresh_rate != 0)
+    }
+
+    pub fn get_physical_dimensions(&self) -> Option<(u32, u32)> {
+        match self.mgr.with_info(&self.proxy, |_, info| info.physical_size) {
+            Some((width, height)) if width > 0 && height > 0 => Some((width as u32, height as u32)),
+            _ => None,
+        }
+    }
+
+    pub fn get_transform(&self) -> MonitorTransform {
+        self.mgr
+            .with_info(&self.proxy, |_, info| match info.transform {
+                wl_output::Transform::Normal => MonitorTransform::Normal,
+                wl_output::Transform::_90 => MonitorTransform::Rotate90,
+                wl_output::Transform::_180 => MonitorTransform::Rotate180,
+                wl_output::Transform::_270 => MonitorTransform::Rotate270,
+                wl_output::Transform::Flipped => MonitorTransform::Flipped,
+                wl_output::Transform::Flipped90 => MonitorTransform::Flipped90,
+                wl_output::Transform::Flipped180 => MonitorTransform::Flipped180,
+                wl_output::Transform::Flipped270 => MonitorTransform::Flipped270,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_subpixel_layout(&self) -> SubpixelLayout {
+        self.mgr
+            .with_info(&self.proxy, |_, info| match info.subpixel {
+                wl_output::Subpixel::Unknown => SubpixelLayout::Unknown,
+                wl_output::Subpixel::None => SubpixelLayout::None,
+                wl_output::Subpixel::HorizontalRgb => SubpixelLayout::HorizontalRgb,
+                wl_output::Subpixel::HorizontalBgr => SubpixelLayout::HorizontalBgr,
+                wl_output::Subpixel::VerticalRgb => SubpixelLayout::VerticalRgb,
+                wl_output::Subpixel::VerticalBgr => SubpixelLayout::VerticalBgr,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_manufacturer(&self) -> Option<String> {
+        self.mgr.with_info(&self.proxy, |_, info| info.make.clone())
+    }
+
+    pub fn get_model(&self) -> Option<String> {
+        self.mgr.with_info(&self.proxy, |_, info| info.model.clone())
+    }
+
+    #[inline]
+    pub fn get_serial_number(&self) -> Option<String> {
+        // `wl_output` doesn't expose serial numbers.
+        None
+    }
+
+    pub fn get_persistent_id(&self) -> Option<String> {
+        self.mgr.with_info(&self.proxy, |_, info| {
+            format!("{}-{}", info.make, info.model)
+        })
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> (PhysicalPosition, PhysicalSize) {
+        // Wayland doesn't tell clients where panels are.
+        (self.get_position(), self.get_dimensions())
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &GammaRamp) -> Result<(), String> {
+        Err("Changing the gamma ramp is not yet possible on Wayland.".to_owned())
+    }
+
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        self.mgr
+            .with_info(&self.proxy, |_, info| {
+                info.modes
+                    .iter()
+                    .map(|m| VideoMode {
+                        dimensions: (m.dimensions.0 as u32, m.dimensions.1 as u32),
+                        // `wl_output` reports refresh rates in mHz.
+                        refresh_rate: ((m.refresh_rate as f64) / 1000.0).round() as u16,
+                        // `wl_output` doesn't tell us about color depth. Report the 24 bits
+                        // of color that X11 reports for the same displays.
+                        bit_depth: 24,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 pub fn get_primary_monitor(outputs: &OutputMgr) -> MonitorId {
@@ -1,16 +1,27 @@
-use std::sync::{Arc, Mutex};
+use std::cmp;
+use std::ptr;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use super::{make_wid, DeviceId, EventsLoopProxy, EventsLoopSink};
+use libc;
+
+use platform::platform::evdev;
+use platform::platform::xkbcommon::Keymap;
+
+use super::{make_wid, DeviceId, EventsLoopProxy, EventsLoopSink, WindowId};
+use super::event_loop::InputState;
 use sctk::keyboard::{
     self, map_keyboard_auto_with_repeat, Event as KbEvent, KeyRepeatEvent, KeyRepeatKind,
 };
 use sctk::reexports::client::protocol::wl_keyboard;
 use sctk::reexports::client::{NewProxy, Proxy};
-use {ElementState, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent};
+use {ElementState, Event, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent};
 
 pub fn init_keyboard(
     keyboard: NewProxy<wl_keyboard::WlKeyboard>,
     sink: Arc<Mutex<EventsLoopSink>>,
+    input_state: Arc<Mutex<InputState>>,
     events_loop_proxy: EventsLoopProxy,
 ) -> Proxy<wl_keyboard::WlKeyboard> {
     // { variables to be captured by the closures
@@ -18,26 +29,40 @@ pub fn init_keyboard(
     let my_sink = sink.clone();
     let repeat_sink = sink.clone();
     let repeat_target = target.clone();
+    let fallback_proxy = events_loop_proxy.clone();
+    // (rawkey, keysym) of the keys held down while we have focus
+    let mut held_keys: Vec<(u32, u32)> = Vec::new();
     // }
     let ret = map_keyboard_auto_with_repeat(
         keyboard,
         KeyRepeatKind::System,
         move |evt: KbEvent, _| match evt {
-            KbEvent::Enter { surface, .. } => {
+            KbEvent::Enter { surface, rawkeys, keysyms, .. } => {
                 let wid = make_wid(&surface);
-                my_sink
-                    .lock()
-                    .unwrap()
-                    .send_event(WindowEvent::Focused(true), wid);
+                // The compositor tells us which keys were already held when we gained focus.
+                input_state.lock().unwrap().keys = rawkeys
+                    .iter()
+                    .zip(keysyms)
+                    .filter_map(|(&rawkey, &keysym)| key_to_vkey(rawkey, keysym))
+                    .collect();
+                held_keys = rawkeys.iter().cloned().zip(keysyms.iter().cloned()).collect();
+                let mut sink = my_sink.lock().unwrap();
+                sink.send_event(WindowEvent::Focused(true), wid);
+                for &(rawkey, keysym) in &held_keys {
+                    send_synthetic_key(&mut sink, wid, rawkey, key_to_vkey(rawkey, keysym), ElementState::Pressed);
+                }
                 *target.lock().unwrap() = Some(wid);
             }
             KbEvent::Leave { surface, .. } => {
                 let wid = make_wid(&surface);
-                my_sink
-                    .lock()
-                    .unwrap()
-                    .send_event(WindowEvent::Focused(false), wid);
+                // The compositor won't tell us about these keys being released anymore.
+                let mut sink = my_sink.lock().unwrap();
+                for (rawkey, keysym) in held_keys.drain(..) {
+                    send_synthetic_key(&mut sink, wid, rawkey, key_to_vkey(rawkey, keysym), ElementState::Released);
+                }
+                sink.send_event(WindowEvent::Focused(false), wid);
                 *target.lock().unwrap() = None;
+                input_state.lock().unwrap().keys.clear();
             }
             KbEvent::Key {
                 modifiers,
@@ -53,16 +78,30 @@ pub fn init_keyboard(
                         wl_keyboard::KeyState::Released => ElementState::Released,
                     };
                     let vkcode = key_to_vkey(rawkey, keysym);
+                    held_keys.retain(|&(held, _)| held != rawkey);
+                    if state == ElementState::Pressed {
+                        held_keys.push((rawkey, keysym));
+                    }
+                    if let Some(vkcode) = vkcode {
+                        let mut input_state = input_state.lock().unwrap();
+                        match state {
+                            ElementState::Pressed => input_state.keys.insert(vkcode),
+                            ElementState::Released => input_state.keys.remove(&vkcode),
+                        };
+                    }
                     let mut guard = my_sink.lock().unwrap();
                     guard.send_event(
                         WindowEvent::KeyboardInput {
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                             input: KeyboardInput {
                                 state: state,
+                                is_repeat: false,
                                 scancode: rawkey,
                                 virtual_keycode: vkcode,
+                                physical_key: evdev::scancode_to_physical_key(rawkey),
                                 modifiers: modifiers.into(),
                             },
+                            is_synthetic: false,
                         },
                         wid,
                     );
@@ -89,10 +128,13 @@ pub fn init_keyboard(
                         device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                         input: KeyboardInput {
                             state: state,
+                            is_repeat: true,
                             scancode: repeat_event.rawkey,
                             virtual_keycode: vkcode,
+                            physical_key: evdev::scancode_to_physical_key(repeat_event.rawkey),
                             modifiers: repeat_event.modifiers.into(),
                         },
+                        is_synthetic: false,
                     },
                     wid,
                 );
@@ -118,23 +160,35 @@ pub fn init_keyboard(
 
             // { variables to be captured by the closure
             let mut target = None;
+            let mut held_keys: Vec<u32> = Vec::new();
             let my_sink = sink;
+            let repeat = KeyRepeater::new(my_sink.clone(), fallback_proxy);
             // }
             keyboard.implement(move |evt, _| match evt {
-                wl_keyboard::Event::Enter { surface, .. } => {
+                wl_keyboard::Event::Enter { surface, keys, .. } => {
                     let wid = make_wid(&surface);
-                    my_sink
-                        .lock()
-                        .unwrap()
-                        .send_event(WindowEvent::Focused(true), wid);
+                    // `keys` is an array of native-endian `u32` scancodes.
+                    held_keys = keys
+                        .chunks(4)
+                        .filter(|chunk| chunk.len() == 4)
+                        .map(|chunk| unsafe { ptr::read_unaligned(chunk.as_ptr() as *const u32) })
+                        .collect();
+                    let mut sink = my_sink.lock().unwrap();
+                    sink.send_event(WindowEvent::Focused(true), wid);
+                    for &key in &held_keys {
+                        send_synthetic_key(&mut sink, wid, key, None, ElementState::Pressed);
+                    }
                     target = Some(wid);
                 }
                 wl_keyboard::Event::Leave { surface, .. } => {
+                    // Repeating stops before the releases are sent, so that no repeat follows them.
+                    repeat.stop();
                     let wid = make_wid(&surface);
-                    my_sink
-                        .lock()
-                        .unwrap()
-                        .send_event(WindowEvent::Focused(false), wid);
+                    let mut sink = my_sink.lock().unwrap();
+                    for key in held_keys.drain(..) {
+                        send_synthetic_key(&mut sink, wid, key, None, ElementState::Released);
+                    }
+                    sink.send_event(WindowEvent::Focused(false), wid);
                     target = None;
                 }
                 wl_keyboard::Event::Key { key, state, .. } => {
@@ -143,26 +197,233 @@ pub fn init_keyboard(
                             wl_keyboard::KeyState::Pressed => ElementState::Pressed,
                             wl_keyboard::KeyState::Released => ElementState::Released,
                         };
+                        held_keys.retain(|&held| held != key);
+                        if state == ElementState::Pressed {
+                            held_keys.push(key);
+                        } else {
+                            repeat.stop_key(key);
+                        }
                         my_sink.lock().unwrap().send_event(
                             WindowEvent::KeyboardInput {
                                 device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                                 input: KeyboardInput {
                                     state: state,
+                                    is_repeat: false,
                                     scancode: key,
                                     virtual_keycode: None,
+                                    physical_key: evdev::scancode_to_physical_key(key),
                                     modifiers: ModifiersState::default(),
                                 },
+                                is_synthetic: false,
                             },
                             wid,
                         );
+                        if state == ElementState::Pressed {
+                            repeat.start(key, wid);
+                        }
                     }
                 }
+                wl_keyboard::Event::RepeatInfo { rate, delay } => {
+                    repeat.set_info(rate.max(0) as u32, delay.max(0) as u32);
+                }
                 _ => (),
             })
         }
     }
 }
 
+// Reports a key whose state changed while we didn't have focus.
+fn send_synthetic_key(
+    sink: &mut EventsLoopSink,
+    wid: WindowId,
+    rawkey: u32,
+    virtual_keycode: Option<VirtualKeyCode>,
+    state: ElementState,
+) {
+    sink.send_event(
+        WindowEvent::KeyboardInput {
+            device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+            input: KeyboardInput {
+                state,
+                is_repeat: false,
+                scancode: rawkey,
+                virtual_keycode,
+                physical_key: evdev::scancode_to_physical_key(rawkey),
+                modifiers: ModifiersState::default(),
+            },
+            is_synthetic: true,
+        },
+        wid,
+    );
+}
+
+// Smithay client toolkit keeps the keymap to itself, so we bind a second keyboard on the same
+// seat to follow keymap and layout changes.
+pub fn init_layout_tracker(
+    keyboard: NewProxy<wl_keyboard::WlKeyboard>,
+    sink: Arc<Mutex<EventsLoopSink>>,
+    keymap: Arc<Mutex<Option<Keymap>>>,
+) -> Proxy<wl_keyboard::WlKeyboard> {
+    keyboard.implement(move |evt, _| match evt {
+        wl_keyboard::Event::Keymap { format, fd, size } => {
+            let new_keymap = match format {
+                wl_keyboard::KeymapFormat::XkbV1 => Keymap::from_fd(fd, size),
+                _ => None,
+            };
+            unsafe { libc::close(fd) };
+            let mut keymap = keymap.lock().unwrap();
+            // The first keymap is just the initial state, not a change.
+            let changed = keymap.is_some();
+            *keymap = new_keymap;
+            if changed {
+                sink.lock().unwrap().send_raw_event(Event::KeyboardLayoutChanged);
+            }
+        }
+        wl_keyboard::Event::Modifiers { group, .. } => {
+            let changed = match *keymap.lock().unwrap() {
+                Some(ref mut keymap) => keymap.update_group(group),
+                None => false,
+            };
+            if changed {
+                sink.lock().unwrap().send_raw_event(Event::KeyboardLayoutChanged);
+            }
+        }
+        _ => (),
+    })
+}
+
+// Client-side key repeat state, used when smithay client toolkit can't repeat keys for us.
+struct KeyRepeat {
+    // Repeats per second, as sent by `wl_keyboard.repeat_info`. A rate of 0 disables repeating.
+    rate: u32,
+    // Delay in milliseconds before repeating starts.
+    delay: u32,
+    // The key currently being repeated, the window it goes to, and when it next repeats.
+    current: Option<(u32, WindowId, Instant)>,
+    // Set when the keyboard goes away, to stop the repeat thread.
+    shutdown: bool,
+}
+
+impl KeyRepeat {
+    fn interval(&self) -> Duration {
+        // A rate above 1000 would otherwise round down to 0ms and spin the repeat thread.
+        Duration::from_millis(cmp::max(1000 / self.rate as u64, 1))
+    }
+}
+
+// Owns the single thread that sends repeated key presses for the fallback keyboard.
+struct KeyRepeater {
+    state: Arc<(Mutex<KeyRepeat>, Condvar)>,
+}
+
+impl KeyRepeater {
+    fn new(sink: Arc<Mutex<EventsLoopSink>>, events_loop_proxy: EventsLoopProxy) -> KeyRepeater {
+        // These are the defaults used by most compositors, and apply until `repeat_info` is received.
+        let state = Arc::new((
+            Mutex::new(KeyRepeat {
+                rate: 25,
+                delay: 600,
+                current: None,
+                shutdown: false,
+            }),
+            Condvar::new(),
+        ));
+        let thread_state = state.clone();
+        thread::spawn(move || run_key_repeat(&thread_state, sink, events_loop_proxy));
+        KeyRepeater { state }
+    }
+
+    fn set_info(&self, rate: u32, delay: u32) {
+        let mut repeat = self.state.0.lock().unwrap();
+        repeat.rate = rate;
+        repeat.delay = delay;
+        if rate == 0 {
+            repeat.current = None;
+        }
+    }
+
+    fn start(&self, key: u32, wid: WindowId) {
+        let mut repeat = self.state.0.lock().unwrap();
+        repeat.current = if repeat.rate == 0 {
+            None
+        } else {
+            let next = Instant::now() + Duration::from_millis(repeat.delay as u64);
+            Some((key, wid, next))
+        };
+        self.state.1.notify_one();
+    }
+
+    fn stop(&self) {
+        self.state.0.lock().unwrap().current = None;
+        self.state.1.notify_one();
+    }
+
+    fn stop_key(&self, key: u32) {
+        let mut repeat = self.state.0.lock().unwrap();
+        if let Some((current_key, _, _)) = repeat.current {
+            if current_key == key {
+                repeat.current = None;
+                self.state.1.notify_one();
+            }
+        }
+    }
+}
+
+impl Drop for KeyRepeater {
+    fn drop(&mut self) {
+        self.state.0.lock().unwrap().shutdown = true;
+        self.state.1.notify_one();
+    }
+}
+
+fn run_key_repeat(
+    state: &(Mutex<KeyRepeat>, Condvar),
+    sink: Arc<Mutex<EventsLoopSink>>,
+    events_loop_proxy: EventsLoopProxy,
+) {
+    let (ref lock, ref condvar) = *state;
+    let mut repeat = lock.lock().unwrap();
+    loop {
+        if repeat.shutdown {
+            return;
+        }
+        let (key, wid, next) = match repeat.current {
+            Some(current) => current,
+            None => {
+                repeat = condvar.wait(repeat).unwrap();
+                continue;
+            },
+        };
+        let now = Instant::now();
+        if now < next {
+            repeat = condvar.wait_timeout(repeat, next - now).unwrap().0;
+            continue;
+        }
+        let interval = repeat.interval();
+        repeat.current = Some((key, wid, now + interval));
+        // The lock is held while sending, so that a release handled meanwhile can't come before
+        // this repeat.
+        sink.lock().unwrap().send_event(
+            WindowEvent::KeyboardInput {
+                device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+                input: KeyboardInput {
+                    state: ElementState::Pressed,
+                    is_repeat: true,
+                    scancode: key,
+                    virtual_keycode: None,
+                    physical_key: evdev::scancode_to_physical_key(key),
+                    modifiers: ModifiersState::default(),
+                },
+                is_synthetic: false,
+            },
+            wid,
+        );
+        if events_loop_proxy.wakeup().is_err() {
+            return;
+        }
+    }
+}
+
 fn key_to_vkey(rawkey: u32, keysym: u32) -> Option<VirtualKeyCode> {
     match rawkey {
         1 => Some(VirtualKeyCode::Escape),
@@ -4,7 +4,7 @@ use {ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
 use events::ModifiersState;
 
 use super::DeviceId;
-use super::event_loop::EventsLoopSink;
+use super::event_loop::{EventsLoopSink, InputState};
 use super::window::WindowStore;
 
 use sctk::reexports::client::{NewProxy, Proxy};
@@ -14,6 +14,7 @@ pub fn implement_pointer(
     pointer: NewProxy<WlPointer>,
     sink: Arc<Mutex<EventsLoopSink>>,
     store: Arc<Mutex<WindowStore>>,
+    input_state: Arc<Mutex<InputState>>,
 ) -> Proxy<WlPointer> {
     let mut mouse_focus = None;
     let mut axis_buffer = None;
@@ -52,6 +53,8 @@ pub fn implement_pointer(
             }
             PtrEvent::Leave { surface, .. } => {
                 mouse_focus = None;
+                // We won't hear about buttons being released from now on.
+                input_state.lock().unwrap().mouse_buttons.clear();
                 let wid = store.find_wid(&surface);
                 if let Some(wid) = wid {
                     sink.send_event(
@@ -92,6 +95,13 @@ pub fn implement_pointer(
                         // TODO figure out the translation ?
                         _ => return,
                     };
+                    {
+                        let mut input_state = input_state.lock().unwrap();
+                        match state {
+                            ElementState::Pressed => input_state.mouse_buttons.insert(button),
+                            ElementState::Released => input_state.mouse_buttons.remove(&button),
+                        };
+                    }
                     sink.send_event(
                         WindowEvent::MouseInput {
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
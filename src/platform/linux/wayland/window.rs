@@ -1,16 +1,23 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
 
-use {CreationError, MouseCursor, WindowAttributes};
+use {CreationError, HitTestResult, MouseCursor, Rect, ResizeDirection, UserAttentionType, VideoMode, WindowAttributes, WindowState};
 use dpi::{LogicalPosition, LogicalSize};
 use platform::MonitorId as PlatformMonitorId;
 use window::MonitorId as RootMonitorId;
 
-use sctk::window::{BasicFrame, Event as WEvent, Window as SWindow};
+use sctk::window::{BasicFrame, Event as WEvent, State as WState, Window as SWindow};
 use sctk::reexports::client::{Display, Proxy};
-use sctk::reexports::client::protocol::{wl_seat, wl_surface, wl_output};
+use sctk::reexports::client::protocol::{wl_callback, wl_compositor, wl_seat, wl_surface, wl_output};
+use sctk::reexports::client::protocol::wl_region::RequestsTrait as RegionRequests;
 use sctk::reexports::client::protocol::wl_compositor::RequestsTrait as CompositorRequests;
 use sctk::reexports::client::protocol::wl_surface::RequestsTrait as SurfaceRequests;
+use sctk::reexports::protocols::unstable::keyboard_shortcuts_inhibit::v1::client::{
+    zwp_keyboard_shortcuts_inhibit_manager_v1, zwp_keyboard_shortcuts_inhibitor_v1,
+};
+use self::zwp_keyboard_shortcuts_inhibit_manager_v1::RequestsTrait as InhibitManagerRequests;
+use self::zwp_keyboard_shortcuts_inhibitor_v1::RequestsTrait as InhibitorRequests;
 use sctk::output::OutputMgr;
 
 use super::{make_wid, EventsLoop, MonitorId, WindowId};
@@ -18,13 +25,19 @@ use platform::platform::wayland::event_loop::{get_available_monitors, get_primar
 
 pub struct Window {
     surface: Proxy<wl_surface::WlSurface>,
+    compositor: Proxy<wl_compositor::WlCompositor>,
     frame: Arc<Mutex<SWindow<BasicFrame>>>,
     monitors: Arc<Mutex<MonitorList>>, // Monitors this window is currently on
     outputs: OutputMgr, // Access to info for all monitors
     size: Arc<Mutex<(u32, u32)>>,
+    state: Arc<Mutex<WindowState>>,
     kill_switch: (Arc<Mutex<bool>>, Arc<Mutex<bool>>),
     display: Arc<Display>,
     need_frame_refresh: Arc<Mutex<bool>>,
+    seats: Arc<Mutex<Vec<(u32, Proxy<wl_seat::WlSeat>)>>>,
+    shortcuts_inhibit_manager: Option<Proxy<zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1>>,
+    // One inhibitor per seat while the keyboard is grabbed
+    shortcuts_inhibitors: Mutex<Vec<Proxy<zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1>>>,
 }
 
 impl Window {
@@ -32,6 +45,8 @@ impl Window {
         let (width, height) = attributes.dimensions.map(Into::into).unwrap_or((800, 600));
         // Create the window
         let size = Arc::new(Mutex::new((width, height)));
+        // Surfaces can't be hidden, so they're visible as soon as they exist.
+        let state = Arc::new(Mutex::new(WindowState { visible: true, ..WindowState::default() }));
 
         // monitor tracking
         let monitor_list = Arc::new(Mutex::new(MonitorList::new()));
@@ -74,11 +89,12 @@ impl Window {
             surface.clone(),
             (width, height),
             move |event, ()| match event {
-                WEvent::Configure { new_size, .. } => {
+                WEvent::Configure { new_size, states } => {
                     let mut store = window_store.lock().unwrap();
                     for window in &mut store.windows {
                         if window.surface.equals(&my_surface) {
                             window.newsize = new_size;
+                            window.update_state(&states);
                             window.need_refresh = true;
                             *(window.need_frame_refresh.lock().unwrap()) = true;
                             return;
@@ -137,6 +153,8 @@ impl Window {
             closed: false,
             newsize: None,
             size: size.clone(),
+            state: state.clone(),
+            new_state: None,
             need_refresh: false,
             need_frame_refresh: need_frame_refresh.clone(),
             surface: surface.clone(),
@@ -144,18 +162,30 @@ impl Window {
             frame: Arc::downgrade(&frame),
             current_dpi: 1,
             new_dpi: None,
+            frame_callback: Arc::new(Mutex::new(FrameCallback::default())),
+            occluded: false,
         });
         evlp.evq.borrow_mut().sync_roundtrip().unwrap();
 
+        // Minimizing is left until the compositor has configured the toplevel
+        if attributes.minimized {
+            frame.lock().unwrap().set_minimized();
+        }
+
         Ok(Window {
             display: evlp.display.clone(),
             surface: surface,
+            compositor: evlp.env.compositor.clone(),
             frame: frame,
             monitors: monitor_list,
             outputs: evlp.env.outputs.clone(),
             size: size,
+            state: state,
             kill_switch: (kill_switch, evlp.cleanup_needed.clone()),
             need_frame_refresh: need_frame_refresh,
+            seats: evlp.seats.clone(),
+            shortcuts_inhibit_manager: evlp.shortcuts_inhibit_manager.clone(),
+            shortcuts_inhibitors: Mutex::new(Vec::new()),
         })
     }
 
@@ -178,6 +208,11 @@ impl Window {
         // TODO
     }
 
+    #[inline]
+    pub fn focus(&self) {
+        warn!("`Window::focus` has no effect on Wayland");
+    }
+
     #[inline]
     pub fn get_position(&self) -> Option<LogicalPosition> {
         // Not possible with wayland
@@ -239,6 +274,38 @@ impl Window {
         *(self.need_frame_refresh.lock().unwrap()) = true;
     }
 
+    pub fn set_opacity(&self, _opacity: f32) {
+        warn!("`Window::set_opacity` has no effect on Wayland");
+    }
+
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        // This needs xdg-activation, which isn't available to us yet
+        if request_type.is_some() {
+            warn!("`Window::request_user_attention` has no effect on Wayland");
+        }
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        self.state.lock().unwrap().maximized
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        // Not possible with wayland
+        false
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.state.lock().unwrap().fullscreen
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.state.lock().unwrap().focused
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.state.lock().unwrap().visible
+    }
+
     pub fn set_maximized(&self, maximized: bool) {
         if maximized {
             self.frame.lock().unwrap().set_maximized();
@@ -247,6 +314,13 @@ impl Window {
         }
     }
 
+    pub fn set_minimized(&self, minimized: bool) {
+        // An xdg_toplevel can't be restored by the client once minimized
+        if minimized {
+            self.frame.lock().unwrap().set_minimized();
+        }
+    }
+
     pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
         if let Some(RootMonitorId {
             inner: PlatformMonitorId::Wayland(ref monitor_id),
@@ -261,6 +335,16 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_fullscreen_exclusive(&self, _monitor: RootMonitorId, _video_mode: VideoMode) -> Result<(), String> {
+        Err("Changing the video mode is not possible on Wayland.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_fullscreen_monitors(&self, _monitors: &[RootMonitorId]) -> Result<(), String> {
+        Err("Spanning fullscreen across monitors is not possible on Wayland.".to_owned())
+    }
+
     #[inline]
     pub fn set_cursor(&self, _cursor: MouseCursor) {
         // TODO
@@ -276,6 +360,75 @@ impl Window {
         Err("Cursor grabbing is not yet possible on Wayland.".to_owned())
     }
 
+    pub fn grab_keyboard(&self, grab: bool) -> Result<(), String> {
+        let manager = self.shortcuts_inhibit_manager
+            .as_ref()
+            .ok_or_else(|| "The compositor doesn't support keyboard shortcuts inhibition".to_owned())?;
+        let mut inhibitors = self.shortcuts_inhibitors.lock().unwrap();
+        if grab == !inhibitors.is_empty() {
+            return Ok(());
+        }
+        if grab {
+            for &(_, ref seat) in self.seats.lock().unwrap().iter() {
+                let inhibitor = manager
+                    .inhibit_shortcuts(&self.surface, seat)
+                    .map_err(|()| "Failed to inhibit keyboard shortcuts".to_owned())?
+                    .implement(|_, _| {});
+                inhibitors.push(inhibitor);
+            }
+        } else {
+            for inhibitor in inhibitors.drain(..) {
+                inhibitor.destroy();
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), String> {
+        Err("Moving the window is not yet possible on Wayland.".to_owned())
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), String> {
+        Err("Resizing the window is not yet possible on Wayland.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_hit_test(&self, _hit_test: Option<Box<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>) {
+        // TODO: This needs the window to be moved and resized, which isn't possible yet
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: LogicalPosition) -> Result<(), String> {
+        Err("Showing the window menu is not yet possible on Wayland.".to_owned())
+    }
+
+    pub fn set_input_region(&self, region: Option<Vec<Rect>>) -> Result<(), String> {
+        match region {
+            Some(region) => {
+                let wl_region = self.compositor
+                    .create_region()
+                    .map_err(|()| "Failed to create a region".to_owned())?
+                    .implement(|_, _| {});
+                // Surface coordinates are already logical
+                for rect in region {
+                    wl_region.add(
+                        rect.position.x as i32,
+                        rect.position.y as i32,
+                        rect.size.width as i32,
+                        rect.size.height as i32,
+                    );
+                }
+                self.surface.set_input_region(Some(&wl_region));
+                wl_region.destroy();
+            },
+            None => self.surface.set_input_region(None),
+        }
+        // The region is double-buffered, and is applied by the next frame the application commits
+        Ok(())
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, _pos: LogicalPosition) -> Result<(), String> {
         Err("Setting the cursor position is not yet possible on Wayland.".to_owned())
@@ -307,6 +460,9 @@ impl Window {
 
 impl Drop for Window {
     fn drop(&mut self) {
+        for inhibitor in self.shortcuts_inhibitors.lock().unwrap().drain(..) {
+            inhibitor.destroy();
+        }
         *(self.kill_switch.0.lock().unwrap()) = true;
         *(self.kill_switch.1.lock().unwrap()) = true;
     }
@@ -320,13 +476,98 @@ struct InternalWindow {
     surface: Proxy<wl_surface::WlSurface>,
     newsize: Option<(u32, u32)>,
     size: Arc<Mutex<(u32, u32)>>,
+    state: Arc<Mutex<WindowState>>,
+    new_state: Option<WindowState>,
     need_refresh: bool,
     need_frame_refresh: Arc<Mutex<bool>>,
     closed: bool,
     kill_switch: Arc<Mutex<bool>>,
     frame: Weak<Mutex<SWindow<BasicFrame>>>,
     current_dpi: i32,
-    new_dpi: Option<i32>
+    new_dpi: Option<i32>,
+    frame_callback: Arc<Mutex<FrameCallback>>,
+    occluded: bool,
+}
+
+// How long a frame callback can go unanswered before the window is considered occluded.
+const OCCLUSION_TIMEOUT_MS: u64 = 1000;
+
+// The compositor only answers frame callbacks for surfaces it's showing, which is how occlusion
+// is detected.
+#[derive(Default)]
+struct FrameCallback {
+    // When the pending callback was requested, if there is one
+    requested: Option<Instant>,
+    // Whether a callback was answered since the last check
+    done: bool,
+}
+
+impl InternalWindow {
+    // Requests a frame callback, which the commit that's about to happen arms. Callbacks are only
+    // requested when a commit is known to follow, so an application that stops drawing isn't
+    // considered occluded.
+    fn request_frame_callback(&mut self) {
+        let mut frame_callback = self.frame_callback.lock().unwrap();
+        if frame_callback.requested.is_some() {
+            return;
+        }
+        // This fails once the surface has been destroyed
+        if let Ok(callback) = self.surface.frame() {
+            frame_callback.requested = Some(Instant::now());
+            let my_frame_callback = self.frame_callback.clone();
+            callback.implement(move |event, _| match event {
+                wl_callback::Event::Done { .. } => {
+                    let mut frame_callback = my_frame_callback.lock().unwrap();
+                    frame_callback.requested = None;
+                    frame_callback.done = true;
+                }
+            });
+        }
+    }
+
+    fn occlusion_deadline(&self) -> Option<Instant> {
+        if self.occluded {
+            return None;
+        }
+        self.frame_callback
+            .lock()
+            .unwrap()
+            .requested
+            .map(|requested| requested + Duration::from_millis(OCCLUSION_TIMEOUT_MS))
+    }
+
+    fn update_occluded(&mut self) -> Option<bool> {
+        let done = ::std::mem::replace(&mut self.frame_callback.lock().unwrap().done, false);
+        let occluded = if done {
+            // Even a callback that timed out means the window is visible again
+            false
+        } else {
+            match self.occlusion_deadline() {
+                Some(deadline) => Instant::now() >= deadline,
+                None => self.occluded,
+            }
+        };
+        if occluded != self.occluded {
+            self.occluded = occluded;
+            Some(occluded)
+        } else {
+            None
+        }
+    }
+
+    fn update_state(&mut self, states: &[WState]) {
+        let mut state = self.state.lock().unwrap();
+        let new_state = WindowState {
+            maximized: states.contains(&WState::Maximized),
+            fullscreen: states.contains(&WState::Fullscreen),
+            focused: states.contains(&WState::Activated),
+            ..*state
+        };
+        if new_state != *state {
+            *state = new_state;
+            self.new_state = Some(new_state);
+        }
+    }
 }
 
 pub struct WindowStore {
@@ -380,19 +621,46 @@ impl WindowStore {
         }
     }
 
+    // When the earliest pending frame callback times out, making its window occluded.
+    pub fn next_occlusion_deadline(&self) -> Option<Instant> {
+        self.windows
+            .iter()
+            .filter_map(|window| window.occlusion_deadline())
+            .min()
+    }
+
     pub fn for_each<F>(&mut self, mut f: F)
     where
-        F: FnMut(Option<(u32, u32)>, &mut (u32, u32), Option<i32>, bool, bool, bool, WindowId, Option<&mut SWindow<BasicFrame>>),
+        F: FnMut(
+            Option<(u32, u32)>,
+            &mut (u32, u32),
+            Option<i32>,
+            Option<WindowState>,
+            Option<bool>,
+            bool,
+            bool,
+            bool,
+            WindowId,
+            Option<&mut SWindow<BasicFrame>>,
+        ),
     {
         for window in &mut self.windows {
             let opt_arc = window.frame.upgrade();
             let mut opt_mutex_lock = opt_arc.as_ref().map(|m| m.lock().unwrap());
+            let newsize = window.newsize.take();
+            let frame_refresh = ::std::mem::replace(&mut *window.need_frame_refresh.lock().unwrap(), false);
+            // Refreshing the frame is followed by a commit, from us or from the application
+            if opt_mutex_lock.is_some() && (newsize.is_some() || frame_refresh) {
+                window.request_frame_callback();
+            }
             f(
-                window.newsize.take(),
+                newsize,
                 &mut *(window.size.lock().unwrap()),
                 window.new_dpi,
+                window.new_state.take(),
+                window.update_occluded(),
                 window.need_refresh,
-                ::std::mem::replace(&mut *window.need_frame_refresh.lock().unwrap(), false),
+                frame_refresh,
                 window.closed,
                 make_wid(&window.surface),
                 opt_mutex_lock.as_mut().map(|m| &mut **m),
@@ -6,3 +6,5 @@ pub use x11_dl::xinput2::*;
 pub use x11_dl::xlib_xcb::*;
 pub use x11_dl::error::OpenError;
 pub use x11_dl::xrandr::*;
+pub use x11_dl::xinerama::{XineramaScreenInfo, Xlib as Xinerama};
+pub use x11_dl::xfixes::*;
@@ -11,11 +11,12 @@ pub mod util;
 
 pub use self::monitor::MonitorId;
 pub use self::window::UnownedWindow;
+use self::window::WindowStateChange;
 pub use self::xdisplay::{XConnection, XNotSupported, XError};
 
 use std::{mem, ptr, slice};
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::ffi::CStr;
 use std::ops::Deref;
 use std::os::raw::*;
@@ -28,16 +29,22 @@ use {
     ControlFlow,
     CreationError,
     DeviceEvent,
+    ElementState,
     Event,
+    HitTestResult,
     EventsLoopClosed,
     KeyboardInput,
     LogicalPosition,
     LogicalSize,
+    MouseButton,
+    PhysicalPosition,
+    VirtualKeyCode,
     WindowAttributes,
     WindowEvent,
 };
 use events::ModifiersState;
 use platform::PlatformSpecificWindowBuilderAttributes;
+use super::evdev;
 use self::dnd::{Dnd, DndState};
 use self::ime::{ImeReceiver, ImeSender, ImeCreationError, Ime};
 
@@ -49,8 +56,19 @@ pub struct EventsLoop {
     ime_sender: ImeSender,
     ime: RefCell<Ime>,
     randr_event_offset: c_int,
+    // The monitors as of the last RandR event, to tell which ones were added, removed or changed.
+    monitors: RefCell<Vec<MonitorId>>,
+    xkb_event_offset: c_int,
     windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
     devices: RefCell<HashMap<DeviceId, Device>>,
+    // Keycodes of the keys currently held down, used to tell auto-repeated presses apart from fresh ones.
+    held_keys: HashSet<c_uint>,
+    // Keycodes of the grabbed hotkeys currently held down, kept apart from `held_keys` since those
+    // presses aren't delivered to any of our windows.
+    held_hotkeys: HashSet<c_uint>,
+    // Global hotkeys grabbed on the root window, as (keycode, modifier mask), by ID.
+    hotkeys: RefCell<HashMap<u32, (ffi::KeyCode, c_uint)>>,
+    next_hotkey_id: Cell<u32>,
     xi2ext: XExtension,
     pending_wakeup: Arc<AtomicBool>,
     root: ffi::Window,
@@ -90,6 +108,15 @@ impl EventsLoop {
         let randr_event_offset = xconn.select_xrandr_input(root)
             .expect("Failed to query XRandR extension");
 
+        let monitors = RefCell::new(xconn.get_available_monitors());
+
+        let xkb_event_offset = xconn.query_xkb_extension()
+            .expect("X server missing Xkb extension");
+        xconn.select_xkb_events(
+            util::XKB_USE_CORE_KBD,
+            ffi::XkbNewKeyboardNotifyMask | ffi::XkbStateNotifyMask | ffi::XkbNamesNotifyMask,
+        ).expect("Failed to select Xkb events").queue();
+
         let xi2ext = unsafe {
             let mut result = XExtension {
                 opcode: mem::uninitialized(),
@@ -150,8 +177,14 @@ impl EventsLoop {
             ime_sender,
             ime,
             randr_event_offset,
+            monitors,
+            xkb_event_offset,
             windows: Default::default(),
             devices: Default::default(),
+            held_keys: Default::default(),
+            held_hotkeys: Default::default(),
+            hotkeys: Default::default(),
+            next_hotkey_id: Cell::new(0),
             xi2ext,
             pending_wakeup: Default::default(),
             root,
@@ -177,6 +210,77 @@ impl EventsLoop {
         &self.xconn
     }
 
+    pub fn get_pressed_keys(&self) -> Vec<VirtualKeyCode> {
+        self.xconn.query_keymap()
+            .into_iter()
+            .filter_map(|keycode| {
+                let keysym = unsafe {
+                    (self.xconn.xlib.XKeycodeToKeysym)(self.xconn.display, keycode, 0)
+                };
+                events::keysym_to_element(keysym as c_uint)
+            })
+            .collect()
+    }
+
+    pub fn get_pressed_mouse_buttons(&self) -> Vec<MouseButton> {
+        let pointer_state = match self.xconn.query_pointer(self.root, util::VIRTUAL_CORE_POINTER) {
+            Ok(pointer_state) => pointer_state,
+            Err(_) => return Vec::new(),
+        };
+        pointer_state.get_pressed_buttons()
+            .into_iter()
+            .filter_map(|button| match button {
+                0 => None,
+                ffi::Button1 => Some(MouseButton::Left),
+                ffi::Button2 => Some(MouseButton::Middle),
+                ffi::Button3 => Some(MouseButton::Right),
+                // Buttons 4 to 7 are the scroll wheel.
+                4..=7 => None,
+                x => Some(MouseButton::Other(x as u8)),
+            })
+            .collect()
+    }
+
+    pub fn get_cursor_position(&self) -> Option<PhysicalPosition> {
+        self.xconn.query_pointer(self.root, util::VIRTUAL_CORE_POINTER)
+            .ok()
+            .map(|pointer_state| (pointer_state.root_x, pointer_state.root_y).into())
+    }
+
+    pub fn register_global_hotkey(&self, modifiers: ModifiersState, key: VirtualKeyCode) -> Option<u32> {
+        let keycode = self.vkey_to_keycode(key)?;
+        let modifiers = util::modifiers_to_mask(modifiers);
+        // The grab fails with `BadAccess` if another client already grabbed this combination.
+        if self.xconn.grab_key(self.root, keycode, modifiers).sync().is_err() {
+            let _ = self.xconn.ungrab_key(self.root, keycode, modifiers).sync();
+            return None;
+        }
+        let id = self.next_hotkey_id.get();
+        self.next_hotkey_id.set(id + 1);
+        self.hotkeys.borrow_mut().insert(id, (keycode, modifiers));
+        Some(id)
+    }
+
+    pub fn unregister_global_hotkey(&self, id: u32) -> Result<(), String> {
+        if let Some((keycode, modifiers)) = self.hotkeys.borrow_mut().remove(&id) {
+            self.xconn.ungrab_key(self.root, keycode, modifiers)
+                .sync()
+                .map_err(|err| format!("Failed to ungrab key: {:?}", err))?;
+        }
+        Ok(())
+    }
+
+    fn vkey_to_keycode(&self, key: VirtualKeyCode) -> Option<ffi::KeyCode> {
+        (8..256)
+            .map(|keycode: c_uint| keycode as ffi::KeyCode)
+            .find(|&keycode| {
+                let keysym = unsafe {
+                    (self.xconn.xlib.XKeycodeToKeysym)(self.xconn.display, keycode, 0)
+                };
+                events::keysym_to_element(keysym as c_uint) == Some(key)
+            })
+    }
+
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy {
             pending_wakeup: Arc::downgrade(&self.pending_wakeup),
@@ -547,6 +651,36 @@ impl EventsLoop {
                 });
             }
 
+            ffi::PropertyNotify => {
+                let xev: &ffi::XPropertyEvent = xev.as_ref();
+                let net_wm_state_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+                let wm_state_atom = unsafe { self.xconn.get_atom_unchecked(b"WM_STATE\0") };
+                let change = if xev.atom == net_wm_state_atom {
+                    WindowStateChange::NetWmState
+                } else if xev.atom == wm_state_atom {
+                    WindowStateChange::WmState
+                } else {
+                    return;
+                };
+                self.send_window_state_change(xev.window, change, &mut callback);
+                self.send_occlusion_change(xev.window, &mut callback);
+            }
+
+            ffi::MapNotify | ffi::UnmapNotify => {
+                let xev: &ffi::XAnyEvent = xev.as_ref();
+                let visible = xev.type_ == ffi::MapNotify;
+                self.send_window_state_change(xev.window, WindowStateChange::Visible(visible), &mut callback);
+                self.send_occlusion_change(xev.window, &mut callback);
+            }
+
+            ffi::VisibilityNotify => {
+                let xev: &ffi::XVisibilityEvent = xev.as_ref();
+                self.with_window(xev.window, |window| {
+                    window.shared_state.lock().fully_obscured = xev.state == ffi::VisibilityFullyObscured;
+                });
+                self.send_occlusion_change(xev.window, &mut callback);
+            }
+
             ffi::DestroyNotify => {
                 let xev: &ffi::XDestroyWindowEvent = xev.as_ref();
 
@@ -591,6 +725,28 @@ impl EventsLoop {
                 let window = xkev.window;
                 let window_id = mkwid(window);
 
+                // Our windows never receive key events on the root window, so this is one of our global hotkeys.
+                if window == self.root {
+                    let is_repeat = if state == Pressed {
+                        !self.held_hotkeys.insert(xkev.keycode)
+                    } else {
+                        self.held_hotkeys.remove(&xkev.keycode);
+                        return;
+                    };
+                    if !is_repeat {
+                        let modifiers = util::relevant_modifiers(xkev.state);
+                        for (&id, &(keycode, hotkey_modifiers)) in self.hotkeys.borrow().iter() {
+                            if keycode as c_uint == xkev.keycode && hotkey_modifiers == modifiers {
+                                callback(Event::DeviceEvent {
+                                    device_id: mkdid(util::VIRTUAL_CORE_KEYBOARD),
+                                    event: DeviceEvent::HotkeyPressed(id),
+                                });
+                            }
+                        }
+                    }
+                    return;
+                }
+
                 // Standard virtual core keyboard ID. XInput2 needs to be used to get a reliable
                 // value, though this should only be an issue under multiseat configurations.
                 let device = util::VIRTUAL_CORE_KEYBOARD;
@@ -620,16 +776,28 @@ impl EventsLoop {
                     };
                     let virtual_keycode = events::keysym_to_element(keysym as c_uint);
 
+                    // Since we use `XkbSetDetectableAutoRepeat`, auto-repeated keys are delivered as a series of
+                    // `KeyPress` events without any intervening `KeyRelease`.
+                    let is_repeat = if state == Pressed {
+                        !self.held_keys.insert(xkev.keycode)
+                    } else {
+                        self.held_keys.remove(&xkev.keycode);
+                        false
+                    };
+
                     callback(Event::WindowEvent {
                         window_id,
                         event: WindowEvent::KeyboardInput {
                             device_id,
                             input: KeyboardInput {
                                 state,
+                                is_repeat,
                                 scancode: xkev.keycode - 8,
                                 virtual_keycode,
+                                physical_key: evdev::scancode_to_physical_key(xkev.keycode - 8),
                                 modifiers,
                             },
+                            is_synthetic: false,
                         }
                     });
                 }
@@ -684,6 +852,27 @@ impl EventsLoop {
                         } else {
                             Released
                         };
+
+                        if state == Pressed && xev.detail as u32 == ffi::Button1 {
+                            let hit_test = self.with_window(xev.event, |window| {
+                                window.hit_test((xev.event_x, xev.event_y))
+                            });
+                            match hit_test {
+                                Some(HitTestResult::Client) | None => (),
+                                Some(HitTestResult::Close) => {
+                                    callback(Event::WindowEvent { window_id, event: WindowEvent::CloseRequested });
+                                    return;
+                                },
+                                Some(_) => return,
+                            }
+                        } else if state == Released && xev.detail as u32 == ffi::Button1 {
+                            // The window records this on every left button press, so only releases
+                            // of presses that weren't delivered are dropped.
+                            let consumed = self
+                                .with_window(xev.event, |window| window.take_hit_test_consumed_press())
+                                .unwrap_or(false);
+                            if consumed { return; }
+                        }
                         match xev.detail as u32 {
                             ffi::Button1 => callback(Event::WindowEvent {
                                 window_id,
@@ -911,6 +1100,23 @@ impl EventsLoop {
                             .expect("Failed to focus input context");
 
                         callback(Event::WindowEvent { window_id, event: Focused(true) });
+                        self.send_window_state_change(xev.event, WindowStateChange::Focused(true), &mut callback);
+
+                        // Keys pressed while we didn't have focus won't get a `KeyPress`, so we report them ourselves.
+                        let modifiers = ModifiersState::from(xev.mods);
+                        let keycodes: Vec<c_uint> = self.xconn.query_keymap()
+                            .into_iter()
+                            .map(|keycode| keycode as c_uint)
+                            .collect();
+                        self.held_keys = keycodes.iter().cloned().collect();
+                        self.send_synthetic_keys(
+                            window_id,
+                            mkdid(xev.deviceid),
+                            &keycodes,
+                            Pressed,
+                            modifiers,
+                            &mut callback,
+                        );
 
                         // The deviceid for this event is for a keyboard instead of a pointer,
                         // so we have to do a little extra work.
@@ -940,10 +1146,22 @@ impl EventsLoop {
                             .borrow_mut()
                             .unfocus(xev.event)
                             .expect("Failed to unfocus input context");
+                        // Releases of keys held now will go to whichever window gains focus, so we report them
+                        // ourselves.
+                        let keycodes: Vec<c_uint> = self.held_keys.drain().collect();
+                        self.send_synthetic_keys(
+                            mkwid(xev.event),
+                            mkdid(xev.deviceid),
+                            &keycodes,
+                            Released,
+                            ModifiersState::from(xev.mods),
+                            &mut callback,
+                        );
                         callback(Event::WindowEvent {
                             window_id: mkwid(xev.event),
                             event: Focused(false),
-                        })
+                        });
+                        self.send_window_state_change(xev.event, WindowStateChange::Focused(false), &mut callback);
                     }
 
                     ffi::XI_TouchBegin | ffi::XI_TouchUpdate | ffi::XI_TouchEnd => {
@@ -1058,7 +1276,9 @@ impl EventsLoop {
                             event: DeviceEvent::Key(KeyboardInput {
                                 scancode,
                                 virtual_keycode,
+                                physical_key: evdev::scancode_to_physical_key(scancode),
                                 state,
+                                is_repeat: xev.flags & ffi::XIKeyRepeat != 0,
                                 // So, in an ideal world we can use libxkbcommon to get modifiers.
                                 // However, libxkbcommon-x11 isn't as commonly installed as one
                                 // would hope. We can still use the Xkb extension to get
@@ -1088,45 +1308,73 @@ impl EventsLoop {
             },
             _ => {
                 if event_type == self.randr_event_offset {
-                    // In the future, it would be quite easy to emit monitor hotplug events.
-                    let prev_list = monitor::invalidate_cached_monitor_list();
-                    if let Some(prev_list) = prev_list {
-                        let new_list = self.xconn.get_available_monitors();
-                        for new_monitor in new_list {
-                            prev_list
-                                .iter()
-                                .find(|prev_monitor| prev_monitor.name == new_monitor.name)
-                                .map(|prev_monitor| {
-                                    if new_monitor.hidpi_factor != prev_monitor.hidpi_factor {
-                                        for (window_id, window) in self.windows.borrow().iter() {
-                                            if let Some(window) = window.upgrade() {
-                                                // Check if the window is on this monitor
-                                                let monitor = window.get_current_monitor();
-                                                if monitor.name == new_monitor.name {
-                                                    callback(Event::WindowEvent {
-                                                        window_id: mkwid(window_id.0),
-                                                        event: WindowEvent::HiDpiFactorChanged(
-                                                            new_monitor.hidpi_factor
-                                                        ),
-                                                    });
-                                                    let (width, height) = match window.get_inner_size_physical() {
-                                                        Some(result) => result,
-                                                        None => continue,
-                                                    };
-                                                    let (_, _, flusher) = window.adjust_for_dpi(
-                                                        prev_monitor.hidpi_factor,
-                                                        new_monitor.hidpi_factor,
-                                                        width as f64,
-                                                        height as f64,
-                                                    );
-                                                    flusher.queue();
-                                                }
-                                            }
-                                        }
+                    monitor::invalidate_cached_monitor_list();
+                    let new_list = self.xconn.get_available_monitors();
+                    let prev_list = mem::replace(&mut *self.monitors.borrow_mut(), new_list.clone());
+                    for new_monitor in &new_list {
+                        let prev_monitor = match prev_list
+                            .iter()
+                            .find(|prev_monitor| prev_monitor.name == new_monitor.name)
+                        {
+                            Some(prev_monitor) => prev_monitor,
+                            None => {
+                                callback(Event::MonitorAdded(mkmid(new_monitor.clone())));
+                                continue;
+                            },
+                        };
+                        if new_monitor.hidpi_factor != prev_monitor.hidpi_factor {
+                            for (window_id, window) in self.windows.borrow().iter() {
+                                if let Some(window) = window.upgrade() {
+                                    // Check if the window is on this monitor
+                                    let monitor = window.get_current_monitor();
+                                    if monitor.name == new_monitor.name {
+                                        callback(Event::WindowEvent {
+                                            window_id: mkwid(window_id.0),
+                                            event: WindowEvent::HiDpiFactorChanged(
+                                                new_monitor.hidpi_factor
+                                            ),
+                                        });
+                                        let (width, height) = match window.get_inner_size_physical() {
+                                            Some(result) => result,
+                                            None => continue,
+                                        };
+                                        let (_, _, flusher) = window.adjust_for_dpi(
+                                            prev_monitor.hidpi_factor,
+                                            new_monitor.hidpi_factor,
+                                            width as f64,
+                                            height as f64,
+                                        );
+                                        flusher.queue();
                                     }
-                                });
+                                }
+                            }
+                        }
+                        if new_monitor != prev_monitor {
+                            callback(Event::MonitorChanged(mkmid(new_monitor.clone())));
+                        }
+                    }
+                    for prev_monitor in prev_list {
+                        if !new_list.iter().any(|new_monitor| new_monitor.name == prev_monitor.name) {
+                            callback(Event::MonitorRemoved(mkmid(prev_monitor)));
                         }
                     }
+                } else if event_type == self.xkb_event_offset {
+                    let xkb_event: &ffi::XkbAnyEvent = unsafe { &*(xev as *const ffi::XEvent as *const _) };
+                    let layout_changed = match xkb_event.xkb_type {
+                        ffi::XkbNewKeyboardNotify => true,
+                        ffi::XkbStateNotify => {
+                            let xkb_event: &ffi::XkbStateNotifyEvent = unsafe { &*(xev as *const ffi::XEvent as *const _) };
+                            xkb_event.changed as c_ulong & ffi::XkbGroupStateMask as c_ulong != 0
+                        },
+                        ffi::XkbNamesNotify => {
+                            let xkb_event: &ffi::_XkbNamesNotifyEvent = unsafe { &*(xev as *const ffi::XEvent as *const _) };
+                            xkb_event.changed & util::XKB_GROUP_NAMES_MASK != 0
+                        },
+                        _ => false,
+                    };
+                    if layout_changed {
+                        callback(Event::KeyboardLayoutChanged);
+                    }
                 }
             },
         }
@@ -1139,6 +1387,63 @@ impl EventsLoop {
         }
     }
 
+    fn send_synthetic_keys<F>(
+        &self,
+        window_id: ::WindowId,
+        device_id: ::DeviceId,
+        keycodes: &[c_uint],
+        state: ElementState,
+        modifiers: ModifiersState,
+        callback: &mut F,
+    )
+        where F: FnMut(Event)
+    {
+        for &keycode in keycodes {
+            let keysym = unsafe {
+                (self.xconn.xlib.XKeycodeToKeysym)(self.xconn.display, keycode as ffi::KeyCode, 0)
+            };
+            let scancode = keycode - 8;
+            callback(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::KeyboardInput {
+                    device_id,
+                    input: KeyboardInput {
+                        state,
+                        is_repeat: false,
+                        scancode,
+                        virtual_keycode: events::keysym_to_element(keysym as c_uint),
+                        physical_key: evdev::scancode_to_physical_key(scancode),
+                        modifiers,
+                    },
+                    is_synthetic: true,
+                },
+            });
+        }
+    }
+
+    fn send_window_state_change<F>(&self, window: ffi::Window, change: WindowStateChange, callback: &mut F)
+        where F: FnMut(Event)
+    {
+        let window_state = self.with_window(window, |window| window.update_window_state(change));
+        if let Some(Some(window_state)) = window_state {
+            callback(Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::StateChanged(window_state),
+            });
+        }
+    }
+
+    fn send_occlusion_change<F>(&self, window: ffi::Window, callback: &mut F)
+        where F: FnMut(Event)
+    {
+        if let Some(Some(occluded)) = self.with_window(window, |window| window.update_occluded()) {
+            callback(Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::Occluded(occluded),
+            });
+        }
+    }
+
     fn init_device(&self, device: c_int) {
         let mut devices = self.devices.borrow_mut();
         if let Some(info) = DeviceInfo::get(&self.xconn, device) {
@@ -1174,6 +1479,17 @@ impl EventsLoop {
     }
 }
 
+impl Drop for EventsLoop {
+    fn drop(&mut self) {
+        // The X connection outlives us, and so would our key grabs and gamma changes.
+        for (_, (keycode, modifiers)) in self.hotkeys.borrow_mut().drain() {
+            self.xconn.ungrab_key(self.root, keycode, modifiers).queue();
+        }
+        self.xconn.restore_gamma_ramps();
+        let _ = self.xconn.flush_requests();
+    }
+}
+
 impl EventsLoopProxy {
     pub fn wakeup(&self) -> Result<(), EventsLoopClosed> {
         // Update the `EventsLoop`'s `pending_wakeup` flag.
@@ -1278,6 +1594,7 @@ impl Drop for Window {
     fn drop(&mut self) {
         let window = self.deref();
         let xconn = &window.xconn;
+        window.restore_video_mode();
         unsafe {
             (xconn.xlib.XDestroyWindow)(xconn.display, window.id().0);
             // If the window was somehow already destroyed, we'll get a `BadWindow` error, which we don't care about.
@@ -1323,6 +1640,7 @@ struct XExtension {
 
 fn mkwid(w: ffi::Window) -> ::WindowId { ::WindowId(::platform::WindowId::X(WindowId(w))) }
 fn mkdid(w: c_int) -> ::DeviceId { ::DeviceId(::platform::DeviceId::X(DeviceId(w))) }
+fn mkmid(m: MonitorId) -> ::MonitorId { ::MonitorId { inner: ::platform::MonitorId::X(m) } }
 
 #[derive(Debug)]
 struct Device {
@@ -1,11 +1,16 @@
 use std::os::raw::*;
+use std::slice;
+use std::sync::Arc;
 
 use parking_lot::Mutex;
 
-use {PhysicalPosition, PhysicalSize};
+use {GammaRamp, MonitorTransform, PhysicalPosition, PhysicalSize, SubpixelLayout, VideoMode};
+use platform::platform::X11_BACKEND;
 use super::{util, XConnection, XError};
 use super::ffi::{
     RRCrtcChangeNotifyMask,
+    RRMode,
+    RROutput,
     RROutputPropertyNotifyMask,
     RRScreenChangeNotifyMask,
     True,
@@ -40,22 +45,36 @@ pub fn invalidate_cached_monitor_list() -> Option<Vec<MonitorId>> {
     (*MONITORS.lock()).take()
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MonitorId {
     /// The actual id
     id: u32,
     /// The name of the monitor
     pub(crate) name: String,
     /// The size of the monitor
-    dimensions: (u32, u32),
+    pub(crate) dimensions: (u32, u32),
     /// The position of the monitor in the X screen
-    position: (i32, i32),
+    pub(crate) position: (i32, i32),
     /// If the monitor is the primary one
     primary: bool,
     /// The DPI scale factor
     pub(crate) hidpi_factor: f64,
+    /// The refresh rate of the current mode
+    refresh_rate: Option<u16>,
+    /// The size of the monitor in millimetres
+    physical_dimensions: (u32, u32),
+    /// The rotation and reflection of the monitor
+    transform: MonitorTransform,
+    /// The subpixel order of the monitor
+    subpixel_layout: SubpixelLayout,
+    /// The identity of the monitor, if it has EDID
+    edid: Option<util::Edid>,
     /// Used to determine which windows are on this monitor
     pub(crate) rect: util::AaRect,
+    /// The output whose modes get switched for exclusive fullscreen
+    pub(crate) output: RROutput,
+    /// The supported video modes, along with their RandR IDs
+    pub(crate) video_modes: Vec<(RRMode, VideoMode)>,
 }
 
 impl MonitorId {
@@ -66,17 +85,28 @@ impl MonitorId {
         repr: util::MonitorRepr,
         primary: bool,
     ) -> Self {
-        let (name, hidpi_factor) = unsafe { xconn.get_output_info(resources, &repr) };
+        let output_info = unsafe { xconn.get_output_info(resources, &repr) };
+        let (refresh_rate, transform) = unsafe { xconn.get_crtc_state(resources, output_info.crtc) };
         let (dimensions, position) = unsafe { (repr.get_dimensions(), repr.get_position()) };
         let rect = util::AaRect::new(position, dimensions);
+        let output = unsafe { repr.get_output() };
+        let video_modes = unsafe { xconn.get_output_modes(resources, output) };
+        let edid = xconn.get_output_edid(output);
         MonitorId {
             id,
-            name,
-            hidpi_factor,
+            name: output_info.name,
+            hidpi_factor: output_info.hidpi_factor,
+            refresh_rate,
+            physical_dimensions: output_info.physical_dimensions,
+            transform,
+            subpixel_layout: output_info.subpixel_layout,
+            edid,
             dimensions,
             position,
             primary,
             rect,
+            output,
+            video_modes,
         }
     }
 
@@ -101,6 +131,82 @@ impl MonitorId {
     pub fn get_hidpi_factor(&self) -> f64 {
         self.hidpi_factor
     }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        self.refresh_rate
+    }
+
+    pub fn get_physical_dimensions(&self) -> Option<(u32, u32)> {
+        match self.physical_dimensions {
+            (0, _) | (_, 0) => None,
+            physical_dimensions => Some(physical_dimensions),
+        }
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        self.transform
+    }
+
+    #[inline]
+    pub fn get_subpixel_layout(&self) -> SubpixelLayout {
+        self.subpixel_layout
+    }
+
+    pub fn get_manufacturer(&self) -> Option<String> {
+        self.edid.as_ref().map(|edid| edid.manufacturer.clone())
+    }
+
+    pub fn get_model(&self) -> Option<String> {
+        self.edid.as_ref().map(|edid| edid.get_model())
+    }
+
+    pub fn get_serial_number(&self) -> Option<String> {
+        self.edid.as_ref().and_then(|edid| edid.get_serial_number())
+    }
+
+    pub fn get_persistent_id(&self) -> Option<String> {
+        self.edid.as_ref().map(|edid| edid.get_persistent_id())
+    }
+
+    pub fn get_work_area(&self) -> (PhysicalPosition, PhysicalSize) {
+        let (position, dimensions) = match get_xconn() {
+            Some(xconn) => {
+                let root = unsafe { (xconn.xlib.XDefaultRootWindow)(xconn.display) };
+                xconn.get_work_area(root, self.position, self.dimensions)
+            },
+            None => (self.position, self.dimensions),
+        };
+        (position.into(), dimensions.into())
+    }
+
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        let xconn = get_xconn()?;
+        let crtc = xconn.get_output_crtc(self.output)?;
+        xconn.get_crtc_gamma(crtc)
+    }
+
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), String> {
+        let xconn = get_xconn().ok_or_else(|| "The X connection is unavailable".to_owned())?;
+        let crtc = xconn.get_output_crtc(self.output)
+            .ok_or_else(|| "The monitor isn't driven by any CRTC".to_owned())?;
+        xconn.set_crtc_gamma(crtc, ramp)
+    }
+
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        self.video_modes
+            .iter()
+            .map(|&(_, video_mode)| video_mode)
+            .collect()
+    }
+}
+
+// `MonitorId` doesn't hold on to the connection, so queries that can't be cached go through the
+// global one. The lock has to be released before making any requests, since it's also taken by
+// the error handler.
+fn get_xconn() -> Option<Arc<XConnection>> {
+    X11_BACKEND.lock().as_ref().ok().cloned()
 }
 
 impl XConnection {
@@ -216,6 +322,27 @@ impl XConnection {
             .unwrap()
     }
 
+    // The index of the monitor in the Xinerama screen list, which is what EWMH uses to refer to
+    // monitors. Xinerama doesn't know about XRandR outputs, so screens are matched by geometry.
+    pub fn get_xinerama_index(&self, monitor: &MonitorId) -> Option<u32> {
+        let xinerama = self.xinerama.as_ref()?;
+        unsafe {
+            if (xinerama.XineramaIsActive)(self.display) != True {
+                return None;
+            }
+            let mut count = 0;
+            let screens = (xinerama.XineramaQueryScreens)(self.display, &mut count);
+            let screens = util::XSmartPointer::new(self, screens)?;
+            slice::from_raw_parts(screens.ptr, count as usize)
+                .iter()
+                .find(|screen| {
+                    (screen.x_org as i32, screen.y_org as i32) == monitor.position
+                        && (screen.width as u32, screen.height as u32) == monitor.dimensions
+                })
+                .map(|screen| screen.screen_number as u32)
+        }
+    }
+
     #[inline]
     pub fn get_primary_monitor(&self) -> MonitorId {
         self.get_available_monitors()
@@ -0,0 +1,179 @@
+use std::{ptr, slice};
+
+use super::*;
+
+const EDID_HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+const EDID_BLOCK_LENGTH: usize = 128;
+
+// Tags of the display descriptors we care about.
+const DESCRIPTOR_SERIAL: u8 = 0xff;
+const DESCRIPTOR_NAME: u8 = 0xfc;
+
+/// The identifying parts of a monitor's Extended Display Identification Data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edid {
+    /// The three letter PNP ID of the manufacturer, e.g. "DEL".
+    pub manufacturer: String,
+    pub product_code: u16,
+    pub serial_number: u32,
+    /// The monitor name descriptor, if present.
+    pub name: Option<String>,
+    /// The serial number descriptor, if present. Most monitors only fill in one of this and
+    /// `serial_number`.
+    pub serial_string: Option<String>,
+}
+
+impl Edid {
+    pub fn parse(data: &[u8]) -> Option<Edid> {
+        if data.len() < EDID_BLOCK_LENGTH || data[..8] != EDID_HEADER {
+            return None;
+        }
+
+        let packed = (data[8] as u16) << 8 | data[9] as u16;
+        let manufacturer = [10, 5, 0]
+            .iter()
+            .map(|&shift| (((packed >> shift) & 0x1f) as u8 + b'A' - 1) as char)
+            .collect();
+        let product_code = data[10] as u16 | (data[11] as u16) << 8;
+        let serial_number = data[12] as u32
+            | (data[13] as u32) << 8
+            | (data[14] as u32) << 16
+            | (data[15] as u32) << 24;
+
+        let mut name = None;
+        let mut serial_string = None;
+        for descriptor in data[54..126].chunks(18) {
+            // Display descriptors start with zeros where detailed timings have a pixel clock.
+            if descriptor[0] != 0 || descriptor[1] != 0 {
+                continue;
+            }
+            match descriptor[3] {
+                DESCRIPTOR_NAME => name = descriptor_text(&descriptor[5..]),
+                DESCRIPTOR_SERIAL => serial_string = descriptor_text(&descriptor[5..]),
+                _ => (),
+            }
+        }
+
+        Some(Edid {
+            manufacturer,
+            product_code,
+            serial_number,
+            name,
+            serial_string,
+        })
+    }
+
+    pub fn get_model(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{:04X}", self.product_code))
+    }
+
+    pub fn get_serial_number(&self) -> Option<String> {
+        self.serial_string.clone().or_else(|| {
+            if self.serial_number != 0 {
+                Some(self.serial_number.to_string())
+            } else {
+                None
+            }
+        })
+    }
+
+    // The product code is used rather than the name, since it's always there.
+    pub fn get_persistent_id(&self) -> String {
+        format!(
+            "{}-{:04X}-{}",
+            self.manufacturer,
+            self.product_code,
+            self.get_serial_number().unwrap_or_default(),
+        )
+    }
+}
+
+// Descriptor text ends with a newline and is padded with spaces.
+fn descriptor_text(bytes: &[u8]) -> Option<String> {
+    let end = bytes.iter().position(|&b| b == b'\n').unwrap_or(bytes.len());
+    let text = String::from_utf8_lossy(&bytes[..end]).trim().to_owned();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+impl XConnection {
+    pub fn get_output_edid(&self, output: ffi::RROutput) -> Option<Edid> {
+        let edid_atom = unsafe { self.get_atom_unchecked(b"EDID\0") };
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut nitems = 0;
+        let mut bytes_after = 0;
+        let mut data = ptr::null_mut();
+        unsafe {
+            (self.xrandr.XRRGetOutputProperty)(
+                self.display,
+                output,
+                edid_atom,
+                0,
+                // The length is given in 32-bit units, and we only need the base block.
+                (EDID_BLOCK_LENGTH / 4) as c_long,
+                ffi::False,
+                ffi::False,
+                ffi::AnyPropertyType as ffi::Atom,
+                &mut actual_type,
+                &mut actual_format,
+                &mut nitems,
+                &mut bytes_after,
+                &mut data,
+            );
+        }
+        let _ = self.check_errors();
+        let data = XSmartPointer::new(self, data)?;
+        if actual_format != 8 {
+            return None;
+        }
+        let bytes = unsafe { slice::from_raw_parts(data.ptr as *const u8, nitems as usize) };
+        Edid::parse(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Edid;
+
+    // The base block of a Dell U2415.
+    const DELL_U2415: [u8; 128] = [
+        0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x10, 0xac, 0xa4, 0xa0, 0x4c, 0x4c, 0x41, 0x30,
+        0x1a, 0x19, 0x01, 0x04, 0xb5, 0x34, 0x20, 0x78, 0x3a, 0x1d, 0xf5, 0xae, 0x4f, 0x35, 0xb3, 0x25,
+        0x0d, 0x50, 0x54, 0xa5, 0x4b, 0x00, 0x71, 0x4f, 0x81, 0x80, 0xa9, 0x40, 0xd1, 0xc0, 0xd1, 0x00,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x28, 0x3c, 0x80, 0xa0, 0x70, 0xb0, 0x23, 0x40, 0x30, 0x20,
+        0x36, 0x00, 0x06, 0x44, 0x21, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00, 0xff, 0x00, 0x37, 0x4d, 0x54,
+        0x30, 0x31, 0x36, 0x37, 0x4b, 0x30, 0x4c, 0x4c, 0x41, 0x0a, 0x00, 0x00, 0x00, 0xfc, 0x00, 0x44,
+        0x45, 0x4c, 0x4c, 0x20, 0x55, 0x32, 0x34, 0x31, 0x35, 0x0a, 0x20, 0x20, 0x00, 0x00, 0x00, 0xfd,
+        0x00, 0x38, 0x4c, 0x1e, 0x51, 0x11, 0x00, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x01, 0xb2,
+    ];
+
+    #[test]
+    fn parses_identification() {
+        let edid = Edid::parse(&DELL_U2415).unwrap();
+        assert_eq!(edid.manufacturer, "DEL");
+        assert_eq!(edid.product_code, 0xa0a4);
+        assert_eq!(edid.serial_number, 0x3041_4c4c);
+        assert_eq!(edid.name, Some("DELL U2415".to_owned()));
+        assert_eq!(edid.serial_string, Some("7MT0167K0LLA".to_owned()));
+        assert_eq!(edid.get_persistent_id(), "DEL-A0A4-7MT0167K0LLA");
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        assert_eq!(Edid::parse(&DELL_U2415[..127]), None);
+        assert_eq!(Edid::parse(&[]), None);
+    }
+
+    #[test]
+    fn rejects_bad_header() {
+        let mut data = DELL_U2415;
+        data[1] = 0x00;
+        assert_eq!(Edid::parse(&data), None);
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::slice;
+
+use parking_lot::Mutex;
+
+use GammaRamp;
+use super::*;
+
+lazy_static! {
+    // The gamma ramp each CRTC had before we first changed it, so that it can be restored.
+    static ref ORIGINAL_GAMMA_RAMPS: Mutex<HashMap<ffi::RRCrtc, GammaRamp>> = Mutex::default();
+}
+
+impl XConnection {
+    // The CRTC currently driving `output`, if any.
+    pub fn get_output_crtc(&self, output: ffi::RROutput) -> Option<ffi::RRCrtc> {
+        unsafe {
+            let root = (self.xlib.XDefaultRootWindow)(self.display);
+            let resources = (self.xrandr.XRRGetScreenResourcesCurrent)(self.display, root);
+            if resources.is_null() {
+                return None;
+            }
+            let output_info = (self.xrandr.XRRGetOutputInfo)(self.display, resources, output);
+            let crtc = if output_info.is_null() {
+                0
+            } else {
+                let crtc = (*output_info).crtc;
+                (self.xrandr.XRRFreeOutputInfo)(output_info);
+                crtc
+            };
+            (self.xrandr.XRRFreeScreenResources)(resources);
+            if crtc != 0 {
+                Some(crtc)
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn get_crtc_gamma(&self, crtc: ffi::RRCrtc) -> Option<GammaRamp> {
+        unsafe {
+            let gamma = (self.xrandr.XRRGetCrtcGamma)(self.display, crtc);
+            if gamma.is_null() {
+                return None;
+            }
+            let size = (*gamma).size as usize;
+            let ramp = if size > 0 {
+                Some(GammaRamp {
+                    red: slice::from_raw_parts((*gamma).red, size).to_vec(),
+                    green: slice::from_raw_parts((*gamma).green, size).to_vec(),
+                    blue: slice::from_raw_parts((*gamma).blue, size).to_vec(),
+                })
+            } else {
+                None
+            };
+            (self.xrandr.XRRFreeGamma)(gamma);
+            ramp
+        }
+    }
+
+    pub fn set_crtc_gamma(&self, crtc: ffi::RRCrtc, ramp: &GammaRamp) -> Result<(), String> {
+        {
+            let mut original_ramps = ORIGINAL_GAMMA_RAMPS.lock();
+            if !original_ramps.contains_key(&crtc) {
+                if let Some(original_ramp) = self.get_crtc_gamma(crtc) {
+                    original_ramps.insert(crtc, original_ramp);
+                }
+            }
+        }
+        self.write_crtc_gamma(crtc, ramp)
+    }
+
+    fn write_crtc_gamma(&self, crtc: ffi::RRCrtc, ramp: &GammaRamp) -> Result<(), String> {
+        let size = unsafe { (self.xrandr.XRRGetCrtcGammaSize)(self.display, crtc) } as usize;
+        if size == 0 {
+            return Err("The monitor doesn't support gamma ramps".to_owned());
+        }
+        if ramp.red.len() != size || ramp.green.len() != size || ramp.blue.len() != size {
+            return Err(format!("The monitor's gamma ramps must have {} entries", size));
+        }
+        unsafe {
+            let gamma = (self.xrandr.XRRAllocGamma)(size as c_int);
+            if gamma.is_null() {
+                return Err("`XRRAllocGamma` returned NULL".to_owned());
+            }
+            slice::from_raw_parts_mut((*gamma).red, size).copy_from_slice(&ramp.red);
+            slice::from_raw_parts_mut((*gamma).green, size).copy_from_slice(&ramp.green);
+            slice::from_raw_parts_mut((*gamma).blue, size).copy_from_slice(&ramp.blue);
+            (self.xrandr.XRRSetCrtcGamma)(self.display, crtc, gamma);
+            (self.xrandr.XRRFreeGamma)(gamma);
+        }
+        self.flush_requests()
+            .map_err(|err| format!("Failed to call `XRRSetCrtcGamma`: {:?}", err))
+    }
+
+    // Puts back the gamma ramps of every CRTC changed through `set_crtc_gamma`.
+    pub fn restore_gamma_ramps(&self) {
+        let original_ramps: Vec<_> = ORIGINAL_GAMMA_RAMPS.lock().drain().collect();
+        for (crtc, ramp) in original_ramps {
+            // The CRTC may have gone away along with its monitor, in which case there's nothing
+            // left to restore.
+            let _ = self.write_crtc_gamma(crtc, &ramp);
+        }
+    }
+}
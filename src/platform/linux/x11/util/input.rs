@@ -1,4 +1,4 @@
-use std::str;
+use std::{slice, str};
 
 use super::*;
 use events::ModifiersState;
@@ -41,6 +41,19 @@ impl<'a> PointerState<'a> {
     pub fn get_modifier_state(&self) -> ModifiersState {
         self.modifiers.into()
     }
+
+    pub fn get_pressed_buttons(&self) -> Vec<c_uint> {
+        if self.buttons.mask.is_null() {
+            return Vec::new();
+        }
+        let mask = unsafe {
+            slice::from_raw_parts(self.buttons.mask, self.buttons.mask_len as usize)
+        };
+        (0..mask.len() * 8)
+            .filter(|&button| mask[button / 8] & (1 << (button % 8)) != 0)
+            .map(|button| button as c_uint)
+            .collect()
+    }
 }
 
 impl<'a> Drop for PointerState<'a> {
@@ -72,7 +85,6 @@ impl XConnection {
         Flusher::new(self)
     }
 
-    #[allow(dead_code)]
     pub fn select_xkb_events(&self, device_id: c_uint, mask: c_ulong) -> Option<Flusher> {
         let status = unsafe {
             (self.xlib.XkbSelectEvents)(
@@ -0,0 +1,158 @@
+use std::ffi::CStr;
+
+use events::ModifiersState;
+use platform::platform::xkbcommon;
+
+use super::*;
+
+// Xkb constants that x11-dl doesn't define: the device spec of the core keyboard, and the
+// `XkbNamesRec` component mask for the group names.
+pub const XKB_USE_CORE_KBD: c_uint = 0x0100;
+pub const XKB_GROUP_NAMES_MASK: c_uint = 1 << 12;
+
+// Caps Lock and Num Lock each change the modifier state, but shouldn't stop a key grab from triggering.
+const LOCK_MASKS: [c_uint; 4] = [0, ffi::LockMask, ffi::Mod2Mask, ffi::LockMask | ffi::Mod2Mask];
+
+pub fn modifiers_to_mask(modifiers: ModifiersState) -> c_uint {
+    let mut mask = 0;
+    if modifiers.shift {
+        mask |= ffi::ShiftMask;
+    }
+    if modifiers.ctrl {
+        mask |= ffi::ControlMask;
+    }
+    if modifiers.alt {
+        mask |= ffi::Mod1Mask;
+    }
+    if modifiers.logo {
+        mask |= ffi::Mod4Mask;
+    }
+    mask
+}
+
+// Strips the lock modifiers from a key event's state, for comparison with a `modifiers_to_mask` result.
+pub fn relevant_modifiers(state: c_uint) -> c_uint {
+    state & (ffi::ShiftMask | ffi::ControlMask | ffi::Mod1Mask | ffi::Mod4Mask)
+}
+
+impl XConnection {
+    // Returns the first event ID used by the Xkb extension. All Xkb events share this type, and
+    // are told apart by their `xkb_type`.
+    pub fn query_xkb_extension(&self) -> Option<c_int> {
+        let mut opcode = 0;
+        let mut event_base = 0;
+        let mut error_base = 0;
+        let mut major = 1;
+        let mut minor = 0;
+        let present = unsafe {
+            (self.xlib.XkbQueryExtension)(
+                self.display,
+                &mut opcode,
+                &mut event_base,
+                &mut error_base,
+                &mut major,
+                &mut minor,
+            )
+        };
+        if present == ffi::True {
+            Some(event_base)
+        } else {
+            None
+        }
+    }
+
+    // The index of the layout (or "group", in Xkb parlance) currently in effect.
+    pub fn get_xkb_group(&self) -> Result<c_uint, XError> {
+        let mut state: ffi::XkbStateRec = unsafe { mem::zeroed() };
+        unsafe {
+            (self.xlib.XkbGetState)(self.display, XKB_USE_CORE_KBD, &mut state);
+        }
+        self.check_errors()?;
+        Ok(state.group as c_uint)
+    }
+
+    pub fn get_xkb_group_name(&self, group: c_uint) -> Option<String> {
+        if group as usize >= ffi::XkbNumKbdGroups as usize {
+            return None;
+        }
+        unsafe {
+            let desc = (self.xlib.XkbAllocKeyboard)();
+            if desc.is_null() {
+                return None;
+            }
+            (*desc).device_spec = XKB_USE_CORE_KBD as _;
+            let status = (self.xlib.XkbGetNames)(self.display, XKB_GROUP_NAMES_MASK, desc);
+            let atom = if status == ffi::Success as c_int && !(*desc).names.is_null() {
+                (*(*desc).names).groups[group as usize]
+            } else {
+                0
+            };
+            (self.xlib.XkbFreeKeyboard)(desc, 0, ffi::True);
+            if atom == 0 {
+                return None;
+            }
+            let name = XSmartPointer::new(self, (self.xlib.XGetAtomName)(self.display, atom))?;
+            Some(CStr::from_ptr(name.ptr).to_string_lossy().into_owned())
+        }
+    }
+
+    pub fn get_keyboard_layout(&self) -> Option<String> {
+        self.get_xkb_group()
+            .ok()
+            .and_then(|group| self.get_xkb_group_name(group))
+    }
+
+    // The text produced by a key in the active layout, without any modifiers held.
+    pub fn get_key_label(&self, scancode: c_uint) -> Option<String> {
+        let keycode = scancode + 8;
+        if keycode > 255 {
+            return None;
+        }
+        let group = self.get_xkb_group().ok()?;
+        let keysym = unsafe {
+            (self.xlib.XkbKeycodeToKeysym)(self.display, keycode as ffi::KeyCode, group as c_int, 0)
+        };
+        if keysym == 0 {
+            return None;
+        }
+        xkbcommon::keysym_to_label(keysym as u32)
+    }
+
+    // Keycodes of all the keys currently held down, regardless of which window has focus.
+    pub fn query_keymap(&self) -> Vec<ffi::KeyCode> {
+        let mut keys = [0 as c_char; 32];
+        unsafe {
+            (self.xlib.XQueryKeymap)(self.display, keys.as_mut_ptr());
+        }
+        (0..256)
+            .filter(|&keycode| keys[keycode / 8] as u8 & (1 << (keycode % 8)) != 0)
+            .map(|keycode| keycode as ffi::KeyCode)
+            .collect()
+    }
+
+    pub fn grab_key(&self, window: ffi::Window, keycode: ffi::KeyCode, modifiers: c_uint) -> Flusher {
+        for &lock_mask in &LOCK_MASKS {
+            unsafe {
+                (self.xlib.XGrabKey)(
+                    self.display,
+                    keycode as c_int,
+                    modifiers | lock_mask,
+                    window,
+                    ffi::False,
+                    ffi::GrabModeAsync,
+                    ffi::GrabModeAsync,
+                );
+            }
+        }
+        Flusher::new(self)
+    }
+
+    pub fn ungrab_key(&self, window: ffi::Window, keycode: ffi::KeyCode, modifiers: c_uint) -> Flusher {
+        for &lock_mask in &LOCK_MASKS {
+            unsafe {
+                (self.xlib.XUngrabKey)(self.display, keycode as c_int, modifiers | lock_mask, window);
+            }
+        }
+        Flusher::new(self)
+    }
+}
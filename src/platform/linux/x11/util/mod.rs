@@ -3,27 +3,37 @@
 
 mod atom;
 mod client_msg;
+mod edid;
 mod format;
+mod gamma;
 mod geometry;
 mod hint;
 mod icon;
 mod input;
+mod keyboard;
 mod memory;
 mod randr;
+mod shape;
 mod window_property;
 mod wm;
+mod workarea;
 
 pub use self::atom::*;
 pub use self::client_msg::*;
+pub use self::edid::*;
 pub use self::format::*;
+pub use self::gamma::*;
 pub use self::geometry::*;
 pub use self::hint::*;
 pub use self::icon::*;
 pub use self::input::*;
+pub use self::keyboard::*;
 pub use self::memory::*;
 pub use self::randr::*;
+pub use self::shape::*;
 pub use self::window_property::*;
 pub use self::wm::*;
+pub use self::workarea::*;
 
 use std::mem;
 use std::ptr;
@@ -1,9 +1,21 @@
 use std::{env, slice};
 use std::str::FromStr;
 
-use validate_hidpi_factor;
+use {validate_hidpi_factor, MonitorTransform, SubpixelLayout, VideoMode};
 use super::*;
 
+// `XRRModeInfo::modeFlags` bits, which affect how the refresh rate is derived from the timings.
+const RR_INTERLACE: c_ulong = 0x10;
+const RR_DOUBLE_SCAN: c_ulong = 0x20;
+
+// `XRRCrtcInfo::rotation` bits.
+const RR_ROTATE_0: c_ushort = 1;
+const RR_ROTATE_90: c_ushort = 2;
+const RR_ROTATE_180: c_ushort = 4;
+const RR_ROTATE_270: c_ushort = 8;
+const RR_REFLECT_X: c_ushort = 16;
+const RR_REFLECT_Y: c_ushort = 32;
+
 pub fn calc_dpi_factor(
     (width_px, height_px): (u32, u32),
     (width_mm, height_mm): (u64, u64),
@@ -37,6 +49,69 @@ pub fn calc_dpi_factor(
     dpi_factor
 }
 
+// Computed the same way the `xrandr` utility does it, rounded to the nearest Hz.
+pub fn mode_refresh_rate(mode: &ffi::XRRModeInfo) -> u16 {
+    let mut v_total = mode.vTotal as f64;
+    if mode.modeFlags as c_ulong & RR_DOUBLE_SCAN != 0 {
+        v_total *= 2.0;
+    }
+    if mode.modeFlags as c_ulong & RR_INTERLACE != 0 {
+        v_total /= 2.0;
+    }
+    if mode.hTotal == 0 || v_total == 0.0 {
+        return 0;
+    }
+    (mode.dotClock as f64 / (mode.hTotal as f64 * v_total)).round() as u16
+}
+
+// Values of `XRROutputInfo::subpixel_order`, which come from the Render extension.
+fn subpixel_layout(subpixel_order: c_int) -> SubpixelLayout {
+    match subpixel_order {
+        1 => SubpixelLayout::HorizontalRgb,
+        2 => SubpixelLayout::HorizontalBgr,
+        3 => SubpixelLayout::VerticalRgb,
+        4 => SubpixelLayout::VerticalBgr,
+        5 => SubpixelLayout::None,
+        _ => SubpixelLayout::Unknown,
+    }
+}
+
+fn transform(rotation: c_ushort) -> MonitorTransform {
+    // Reflecting along Y is the same as reflecting along X, then rotating by 180 degrees.
+    let (flipped, rotation) = match (rotation & RR_REFLECT_X != 0, rotation & RR_REFLECT_Y != 0) {
+        (true, true) => (false, rotate_by_180(rotation)),
+        (false, true) => (true, rotate_by_180(rotation)),
+        (flipped, false) => (flipped, rotation),
+    };
+    match (flipped, rotation & 0xf) {
+        (false, RR_ROTATE_90) => MonitorTransform::Rotate90,
+        (false, RR_ROTATE_180) => MonitorTransform::Rotate180,
+        (false, RR_ROTATE_270) => MonitorTransform::Rotate270,
+        (false, _) => MonitorTransform::Normal,
+        (true, RR_ROTATE_90) => MonitorTransform::Flipped90,
+        (true, RR_ROTATE_180) => MonitorTransform::Flipped180,
+        (true, RR_ROTATE_270) => MonitorTransform::Flipped270,
+        (true, _) => MonitorTransform::Flipped,
+    }
+}
+
+fn rotate_by_180(rotation: c_ushort) -> c_ushort {
+    match rotation & 0xf {
+        RR_ROTATE_90 => RR_ROTATE_270,
+        RR_ROTATE_180 => RR_ROTATE_0,
+        RR_ROTATE_270 => RR_ROTATE_90,
+        _ => RR_ROTATE_180,
+    }
+}
+
+pub struct OutputInfo {
+    pub name: String,
+    pub hidpi_factor: f64,
+    pub physical_dimensions: (u32, u32),
+    pub subpixel_layout: SubpixelLayout,
+    pub crtc: ffi::RRCrtc,
+}
+
 pub enum MonitorRepr {
     Monitor(*mut ffi::XRRMonitorInfo),
     Crtc(*mut ffi::XRRCrtcInfo),
@@ -79,7 +154,7 @@ impl From<*mut ffi::XRRCrtcInfo> for MonitorRepr {
 }
 
 impl XConnection {
-    pub unsafe fn get_output_info(&self, resources: *mut ffi::XRRScreenResources, repr: &MonitorRepr) -> (String, f64) {
+    pub unsafe fn get_output_info(&self, resources: *mut ffi::XRRScreenResources, repr: &MonitorRepr) -> OutputInfo {
         let output_info = (self.xrandr.XRRGetOutputInfo)(
             self.display,
             resources,
@@ -90,11 +165,138 @@ impl XConnection {
             (*output_info).nameLen as usize,
         );
         let name = String::from_utf8_lossy(name_slice).into();
-        let hidpi_factor = calc_dpi_factor(
-            repr.get_dimensions(),
-            ((*output_info).mm_width as u64, (*output_info).mm_height as u64),
-        );
+        let physical_dimensions = ((*output_info).mm_width as u64, (*output_info).mm_height as u64);
+        let hidpi_factor = calc_dpi_factor(repr.get_dimensions(), physical_dimensions);
+        let info = OutputInfo {
+            name,
+            hidpi_factor,
+            physical_dimensions: (physical_dimensions.0 as u32, physical_dimensions.1 as u32),
+            subpixel_layout: subpixel_layout((*output_info).subpixel_order as c_int),
+            crtc: (*output_info).crtc,
+        };
         (self.xrandr.XRRFreeOutputInfo)(output_info);
-        (name, hidpi_factor)
+        info
+    }
+
+    // Returns the refresh rate and transform of the given CRTC.
+    pub unsafe fn get_crtc_state(
+        &self,
+        resources: *mut ffi::XRRScreenResources,
+        crtc_id: ffi::RRCrtc,
+    ) -> (Option<u16>, MonitorTransform) {
+        if crtc_id == 0 {
+            return (None, MonitorTransform::Normal);
+        }
+        let crtc = (self.xrandr.XRRGetCrtcInfo)(self.display, resources, crtc_id);
+        if crtc.is_null() {
+            return (None, MonitorTransform::Normal);
+        }
+        let refresh_rate = if (*resources).nmode > 0 {
+            slice::from_raw_parts((*resources).modes, (*resources).nmode as usize)
+                .iter()
+                .find(|mode| mode.id == (*crtc).mode)
+                .map(mode_refresh_rate)
+                .filter(|&refresh_rate| refresh_rate != 0)
+        } else {
+            None
+        };
+        let transform = transform((*crtc).rotation as c_ushort);
+        (self.xrandr.XRRFreeCrtcInfo)(crtc);
+        (refresh_rate, transform)
+    }
+
+    // Returns the modes supported by `output`, along with the IDs needed to switch to them.
+    pub unsafe fn get_output_modes(
+        &self,
+        resources: *mut ffi::XRRScreenResources,
+        output: ffi::RROutput,
+    ) -> Vec<(ffi::RRMode, VideoMode)> {
+        let mut modes = Vec::new();
+        let output_info = (self.xrandr.XRRGetOutputInfo)(self.display, resources, output);
+        if output_info.is_null() {
+            return modes;
+        }
+        // RandR doesn't deal in color depths, so every mode gets the screen's.
+        let screen = (self.xlib.XDefaultScreen)(self.display);
+        let bit_depth = (self.xlib.XDefaultDepth)(self.display, screen) as u16;
+        let all_modes = if (*resources).nmode > 0 {
+            slice::from_raw_parts((*resources).modes, (*resources).nmode as usize)
+        } else {
+            &[]
+        };
+        let output_modes = if (*output_info).nmode > 0 {
+            slice::from_raw_parts((*output_info).modes, (*output_info).nmode as usize)
+        } else {
+            &[]
+        };
+        for &id in output_modes {
+            if let Some(mode) = all_modes.iter().find(|mode| mode.id == id) {
+                let video_mode = VideoMode {
+                    dimensions: (mode.width as u32, mode.height as u32),
+                    refresh_rate: mode_refresh_rate(mode),
+                    bit_depth,
+                };
+                // Modes that only differ in their timings are indistinguishable to users.
+                if !modes.iter().any(|&(_, existing)| existing == video_mode) {
+                    modes.push((id, video_mode));
+                }
+            }
+        }
+        (self.xrandr.XRRFreeOutputInfo)(output_info);
+        modes
+    }
+
+    // Switches the CRTC driving `output` to `mode`, keeping its position and rotation. Returns
+    // the mode that was previously set, so that it can be restored later.
+    pub fn set_output_mode(&self, output: ffi::RROutput, mode: ffi::RRMode) -> Result<ffi::RRMode, String> {
+        unsafe {
+            let root = (self.xlib.XDefaultRootWindow)(self.display);
+            let resources = (self.xrandr.XRRGetScreenResourcesCurrent)(self.display, root);
+            if resources.is_null() {
+                return Err("`XRRGetScreenResourcesCurrent` returned NULL".to_owned());
+            }
+            let output_info = (self.xrandr.XRRGetOutputInfo)(self.display, resources, output);
+            let crtc_id = if output_info.is_null() {
+                0
+            } else {
+                let crtc_id = (*output_info).crtc;
+                (self.xrandr.XRRFreeOutputInfo)(output_info);
+                crtc_id
+            };
+            let crtc = if crtc_id == 0 {
+                ptr::null_mut()
+            } else {
+                (self.xrandr.XRRGetCrtcInfo)(self.display, resources, crtc_id)
+            };
+            let result = if crtc.is_null() {
+                Err("The monitor isn't driven by any CRTC".to_owned())
+            } else {
+                let previous_mode = (*crtc).mode;
+                let status = (self.xrandr.XRRSetCrtcConfig)(
+                    self.display,
+                    resources,
+                    crtc_id,
+                    ffi::CurrentTime,
+                    (*crtc).x,
+                    (*crtc).y,
+                    mode,
+                    (*crtc).rotation,
+                    (*crtc).outputs,
+                    (*crtc).noutput,
+                );
+                (self.xrandr.XRRFreeCrtcInfo)(crtc);
+                if status == ffi::Success as c_int {
+                    Ok(previous_mode)
+                } else {
+                    Err(format!("`XRRSetCrtcConfig` failed with status {}", status))
+                }
+            };
+            (self.xrandr.XRRFreeScreenResources)(resources);
+            result.and_then(|previous_mode| {
+                self.check_errors()
+                    .map(|_| previous_mode)
+                    .map_err(|err| format!("Failed to call `XRRSetCrtcConfig`: {:?}", err))
+            })
+        }
     }
 }
@@ -0,0 +1,34 @@
+use super::*;
+
+// `ShapeInput` from the XShape extension, which XFixes uses to refer to the input shape.
+const SHAPE_INPUT: c_int = 2;
+
+impl XConnection {
+    // Restricts the input shape of `window` to `rectangles`, or resets it to cover the whole
+    // window if `None`.
+    pub fn set_input_shape(
+        &self,
+        window: ffi::Window,
+        rectangles: Option<&mut [ffi::XRectangle]>,
+    ) -> Result<(), String> {
+        let xfixes = self.xfixes
+            .as_ref()
+            .ok_or_else(|| "The XFixes extension is not available".to_owned())?;
+        unsafe {
+            let region = match rectangles {
+                Some(rectangles) => (xfixes.XFixesCreateRegion)(
+                    self.display,
+                    rectangles.as_mut_ptr(),
+                    rectangles.len() as c_int,
+                ),
+                None => 0,
+            };
+            (xfixes.XFixesSetWindowShapeRegion)(self.display, window, SHAPE_INPUT, 0, 0, region);
+            if region != 0 {
+                (xfixes.XFixesDestroyRegion)(self.display, region);
+            }
+        }
+        self.flush_requests()
+            .map_err(|err| format!("Failed to set the input shape: {:?}", err))
+    }
+}
@@ -2,6 +2,20 @@ use parking_lot::Mutex;
 
 use super::*;
 
+// The ICCCM `WM_STATE` value of a minimized window, which x11-dl doesn't define.
+pub const ICONIC_STATE: c_int = 3;
+
+// The `_NET_WM_MOVERESIZE` actions.
+pub const MOVERESIZE_TOPLEFT: c_long = 0;
+pub const MOVERESIZE_TOP: c_long = 1;
+pub const MOVERESIZE_TOPRIGHT: c_long = 2;
+pub const MOVERESIZE_RIGHT: c_long = 3;
+pub const MOVERESIZE_BOTTOMRIGHT: c_long = 4;
+pub const MOVERESIZE_BOTTOM: c_long = 5;
+pub const MOVERESIZE_BOTTOMLEFT: c_long = 6;
+pub const MOVERESIZE_LEFT: c_long = 7;
+pub const MOVERESIZE_MOVE: c_long = 8;
+
 // This info is global to the window manager.
 lazy_static! {
     static ref SUPPORTED_HINTS: Mutex<Vec<ffi::Atom>> = Mutex::new(Vec::with_capacity(0));
@@ -0,0 +1,208 @@
+use std::cmp;
+
+use super::*;
+
+// The space a panel or dock reserves at the edges of the root window, in the layout of
+// `_NET_WM_STRUT_PARTIAL`: left, right, top, bottom, followed by the start and end of each
+// edge's reserved range along the other axis.
+type Strut = [i64; 12];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Edges {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl Edges {
+    // Shrinks these edges so that they no longer overlap the space reserved by `strut`.
+    fn exclude_strut(&mut self, strut: &Strut, (root_width, root_height): (i64, i64)) {
+        let bounds = *self;
+        let overlaps_vertically = |start: i64, end: i64| start < bounds.bottom && end >= bounds.top;
+        let overlaps_horizontally = |start: i64, end: i64| start < bounds.right && end >= bounds.left;
+
+        if strut[0] > 0 && overlaps_vertically(strut[4], strut[5]) {
+            self.left = cmp::max(self.left, strut[0]);
+        }
+        if strut[1] > 0 && overlaps_vertically(strut[6], strut[7]) {
+            self.right = cmp::min(self.right, root_width - strut[1]);
+        }
+        if strut[2] > 0 && overlaps_horizontally(strut[8], strut[9]) {
+            self.top = cmp::max(self.top, strut[2]);
+        }
+        if strut[3] > 0 && overlaps_horizontally(strut[10], strut[11]) {
+            self.bottom = cmp::min(self.bottom, root_height - strut[3]);
+        }
+    }
+
+    fn intersect(&mut self, other: &Edges) {
+        self.left = cmp::max(self.left, other.left);
+        self.top = cmp::max(self.top, other.top);
+        self.right = cmp::min(self.right, other.right);
+        self.bottom = cmp::min(self.bottom, other.bottom);
+    }
+}
+
+impl XConnection {
+    // The struts of all the windows managed by the WM, or `None` if the WM doesn't tell us
+    // which windows it manages.
+    fn get_struts(&self, root: ffi::Window) -> Option<Vec<Strut>> {
+        let client_list_atom = unsafe { self.get_atom_unchecked(b"_NET_CLIENT_LIST\0") };
+        let strut_partial_atom = unsafe { self.get_atom_unchecked(b"_NET_WM_STRUT_PARTIAL\0") };
+        let strut_atom = unsafe { self.get_atom_unchecked(b"_NET_WM_STRUT\0") };
+
+        if !hint_is_supported(client_list_atom) {
+            return None;
+        }
+
+        let client_list: Vec<ffi::Window> = self.get_property(
+            root,
+            client_list_atom,
+            ffi::XA_WINDOW,
+        ).ok()?;
+
+        let struts = client_list
+            .into_iter()
+            .filter_map(|window| {
+                let strut_partial: Option<Vec<c_ulong>> = self.get_property(
+                    window,
+                    strut_partial_atom,
+                    ffi::XA_CARDINAL,
+                ).ok();
+                if let Some(strut_partial) = strut_partial.filter(|strut| strut.len() >= 12) {
+                    let mut strut = [0; 12];
+                    for (value, &reserved) in strut.iter_mut().zip(&strut_partial) {
+                        *value = reserved as i64;
+                    }
+                    return Some(strut);
+                }
+                // The older `_NET_WM_STRUT` reserves the whole length of each edge.
+                let strut: Option<Vec<c_ulong>> = self.get_property(
+                    window,
+                    strut_atom,
+                    ffi::XA_CARDINAL,
+                ).ok();
+                strut.filter(|strut| strut.len() >= 4).map(|strut| {
+                    let (left, right, top, bottom) =
+                        (strut[0] as i64, strut[1] as i64, strut[2] as i64, strut[3] as i64);
+                    [left, right, top, bottom, 0, i64::max_value(), 0, i64::max_value(),
+                        0, i64::max_value(), 0, i64::max_value()]
+                })
+            })
+            .collect();
+        Some(struts)
+    }
+
+    // `_NET_WORKAREA` for the current desktop. This spans every monitor, so it's only accurate
+    // when there's a single monitor.
+    fn get_net_workarea(&self, root: ffi::Window) -> Option<Edges> {
+        let workarea_atom = unsafe { self.get_atom_unchecked(b"_NET_WORKAREA\0") };
+        let current_desktop_atom = unsafe { self.get_atom_unchecked(b"_NET_CURRENT_DESKTOP\0") };
+
+        let current_desktop: Vec<c_ulong> = self.get_property(
+            root,
+            current_desktop_atom,
+            ffi::XA_CARDINAL,
+        ).unwrap_or_default();
+        let desktop = current_desktop.get(0).cloned().unwrap_or(0) as usize;
+
+        let workarea: Vec<c_ulong> = self.get_property(
+            root,
+            workarea_atom,
+            ffi::XA_CARDINAL,
+        ).ok()?;
+        let workarea = workarea.get(desktop * 4..desktop * 4 + 4)?;
+        let (x, y) = (workarea[0] as i64, workarea[1] as i64);
+        Some(Edges {
+            left: x,
+            top: y,
+            right: x + workarea[2] as i64,
+            bottom: y + workarea[3] as i64,
+        })
+    }
+
+    // The part of a monitor that isn't covered by panels or docks.
+    pub fn get_work_area(
+        &self,
+        root: ffi::Window,
+        (x, y): (i32, i32),
+        (width, height): (u32, u32),
+    ) -> ((i32, i32), (u32, u32)) {
+        let mut edges = Edges {
+            left: x as i64,
+            top: y as i64,
+            right: x as i64 + width as i64,
+            bottom: y as i64 + height as i64,
+        };
+
+        match (self.get_struts(root), self.get_geometry(root)) {
+            (Some(struts), Ok(root_geometry)) => {
+                let root_size = (root_geometry.width as i64, root_geometry.height as i64);
+                for strut in &struts {
+                    edges.exclude_strut(strut, root_size);
+                }
+            },
+            _ => if let Some(workarea) = self.get_net_workarea(root) {
+                edges.intersect(&workarea);
+            },
+        }
+
+        // If the panels somehow cover the entire monitor, it's more useful to ignore them.
+        if edges.right <= edges.left || edges.bottom <= edges.top {
+            return ((x, y), (width, height));
+        }
+        (
+            (edges.left as i32, edges.top as i32),
+            ((edges.right - edges.left) as u32, (edges.bottom - edges.top) as u32),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edges, Strut};
+
+    // Two 1920x1080 monitors side by side.
+    const ROOT_SIZE: (i64, i64) = (3840, 1080);
+    const LEFT_MONITOR: Edges = Edges { left: 0, top: 0, right: 1920, bottom: 1080 };
+    const RIGHT_MONITOR: Edges = Edges { left: 1920, top: 0, right: 3840, bottom: 1080 };
+
+    fn excluding(mut edges: Edges, strut: &Strut) -> Edges {
+        edges.exclude_strut(strut, ROOT_SIZE);
+        edges
+    }
+
+    #[test]
+    fn top_panel() {
+        // A 32 pixel tall panel along the top of the left monitor.
+        let strut = [0, 0, 32, 0, 0, 0, 0, 0, 0, 1919, 0, 0];
+        assert_eq!(
+            excluding(LEFT_MONITOR, &strut),
+            Edges { left: 0, top: 32, right: 1920, bottom: 1080 },
+        );
+    }
+
+    #[test]
+    fn partial_height_side_dock() {
+        // A 64 pixel wide dock at the left of the left monitor, only spanning its middle.
+        let strut = [64, 0, 0, 0, 300, 779, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            excluding(LEFT_MONITOR, &strut),
+            Edges { left: 64, top: 0, right: 1920, bottom: 1080 },
+        );
+    }
+
+    #[test]
+    fn strut_on_other_monitor() {
+        // A panel along the top of the left monitor, and a dock at the right edge of the right one.
+        let top_panel = [0, 0, 32, 0, 0, 0, 0, 0, 0, 1919, 0, 0];
+        let right_dock = [0, 48, 0, 0, 0, 0, 0, 1079, 0, 0, 0, 0];
+        assert_eq!(excluding(RIGHT_MONITOR, &top_panel), RIGHT_MONITOR);
+        assert_eq!(excluding(LEFT_MONITOR, &right_dock), LEFT_MONITOR);
+        assert_eq!(
+            excluding(RIGHT_MONITOR, &right_dock),
+            Edges { left: 1920, top: 0, right: 3792, bottom: 1080 },
+        );
+    }
+}
@@ -7,7 +7,7 @@ use std::sync::Arc;
 use libc;
 use parking_lot::Mutex;
 
-use {Icon, MouseCursor, WindowAttributes};
+use {HitTestResult, Icon, MouseCursor, Rect, ResizeDirection, UserAttentionType, VideoMode, WindowAttributes, WindowState};
 use CreationError::{self, OsError};
 use dpi::{LogicalPosition, LogicalSize};
 use platform::MonitorId as PlatformMonitorId;
@@ -15,7 +15,7 @@ use platform::PlatformSpecificWindowBuilderAttributes;
 use platform::x11::MonitorId as X11MonitorId;
 use window::MonitorId as RootMonitorId;
 
-use super::{ffi, util, ImeSender, XConnection, XError, WindowId, EventsLoop};
+use super::{ffi, monitor, util, ImeSender, XConnection, XError, WindowId, EventsLoop};
 
 unsafe extern "C" fn visibility_predicate(
     _display: *mut ffi::Display,
@@ -27,6 +27,17 @@ unsafe extern "C" fn visibility_predicate(
     (event.window == window && event.type_ == ffi::VisibilityNotify) as _
 }
 
+// What an event told us about the window's state.
+#[derive(Debug, Clone, Copy)]
+pub enum WindowStateChange {
+    // `_NET_WM_STATE` changed.
+    NetWmState,
+    // `WM_STATE` changed.
+    WmState,
+    Focused(bool),
+    Visible(bool),
+}
+
 #[derive(Debug, Default)]
 pub struct SharedState {
     pub cursor_pos: Option<(f64, f64)>,
@@ -39,6 +50,19 @@ pub struct SharedState {
     pub dpi_adjusted: Option<(f64, f64)>,
     // Used to restore position after exiting fullscreen.
     pub restore_position: Option<(i32, i32)>,
+    // Used to restore the monitor's video mode after exiting exclusive fullscreen.
+    pub restore_video_mode: Option<(ffi::RROutput, ffi::RRMode)>,
+    // Whether the WM was last told to span the window across several monitors in fullscreen.
+    pub spans_monitors: bool,
+    // The state last reported through `WindowEvent::StateChanged`.
+    pub window_state: WindowState,
+    // Whether `_NET_WM_STATE_HIDDEN` and `WM_STATE` each say that the window is minimized.
+    pub net_wm_hidden: bool,
+    pub iconic: bool,
+    // Whether the last `VisibilityNotify` said the window is entirely covered.
+    pub fully_obscured: bool,
+    // The occlusion last reported through `WindowEvent::Occluded`.
+    pub occluded: Option<bool>,
     pub frame_extents: Option<util::FrameExtentsHeuristic>,
     pub min_dimensions: Option<LogicalSize>,
     pub max_dimensions: Option<LogicalSize>,
@@ -62,10 +86,14 @@ pub struct UnownedWindow {
     screen_id: i32, // never changes
     cursor: Mutex<MouseCursor>,
     cursor_grabbed: Mutex<bool>,
+    keyboard_grabbed: Mutex<bool>,
     cursor_hidden: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
     pub multitouch: bool, // never changes
     pub shared_state: Mutex<SharedState>,
+    hit_test: Mutex<Option<Arc<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>>,
+    // Whether the last left mouse button press was consumed by the hit test.
+    hit_test_consumed_press: Mutex<bool>,
 }
 
 impl UnownedWindow {
@@ -156,7 +184,8 @@ impl UnownedWindow {
                 | ffi::KeymapStateMask
                 | ffi::ButtonPressMask
                 | ffi::ButtonReleaseMask
-                | ffi::PointerMotionMask;
+                | ffi::PointerMotionMask
+                | ffi::PropertyChangeMask;
             swa.border_pixel = 0;
             swa.override_redirect = pl_attribs.override_redirect as c_int;
             swa
@@ -199,10 +228,13 @@ impl UnownedWindow {
             screen_id,
             cursor: Default::default(),
             cursor_grabbed: Default::default(),
+            keyboard_grabbed: Default::default(),
             cursor_hidden: Default::default(),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             multitouch: window_attrs.multitouch,
             shared_state: SharedState::new(dpi_factor),
+            hit_test: Default::default(),
+            hit_test_consumed_press: Default::default(),
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -317,6 +349,10 @@ impl UnownedWindow {
                 );
             }//.queue();
 
+            if window_attrs.minimized {
+                window.set_initial_state_inner(util::ICONIC_STATE).queue();
+            }
+
             // Set visibility (map window)
             if window_attrs.visible {
                 unsafe {
@@ -366,6 +402,10 @@ impl UnownedWindow {
                 }
             }
 
+            // `XIfEvent` below discards the first `VisibilityNotify`, so we start from what we
+            // expect the window to look like.
+            window.shared_state.lock().occluded = Some(!window_attrs.visible || window_attrs.minimized);
+
             // These properties must be set after mapping
             if window_attrs.maximized {
                 window.set_maximized_inner(window_attrs.maximized).queue();
@@ -376,8 +416,12 @@ impl UnownedWindow {
             if window_attrs.always_on_top {
                 window.set_always_on_top_inner(window_attrs.always_on_top).queue();
             }
+            if window_attrs.opacity < 1.0 {
+                window.set_opacity_inner(window_attrs.opacity).queue();
+            }
 
-            if window_attrs.visible {
+            // An iconic window never becomes visible, and shouldn't take focus anyway.
+            if window_attrs.visible && !window_attrs.minimized {
                 unsafe {
                     // XSetInputFocus generates an error if the window is not visible, so we wait
                     // until we receive VisibilityNotify.
@@ -411,6 +455,11 @@ impl UnownedWindow {
         LogicalPosition::from_physical((x, y), dpi)
     }
 
+    fn logicalize_position(&self, (x, y): (f64, f64)) -> LogicalPosition {
+        let dpi = self.get_hidpi_factor();
+        LogicalPosition::from_physical((x, y), dpi)
+    }
+
     fn logicalize_size(&self, (width, height): (u32, u32)) -> LogicalSize {
         let dpi = self.get_hidpi_factor();
         LogicalSize::from_physical((width, height), dpi)
@@ -525,20 +574,134 @@ impl UnownedWindow {
                 self.shared_state.lock().restore_position = window_position;
                 let monitor_origin: (i32, i32) = monitor.get_position().into();
                 self.set_position_inner(monitor_origin.0, monitor_origin.1).queue();
+                // The WM would otherwise keep spanning the window across the monitors last given
+                // to `set_fullscreen_monitors`.
+                let spans_monitors = mem::replace(&mut self.shared_state.lock().spans_monitors, false);
+                if spans_monitors {
+                    if let Some(index) = self.xconn.get_xinerama_index(&monitor) {
+                        self.set_fullscreen_monitors_hint(index, index, index, index).queue();
+                    }
+                }
                 self.set_fullscreen_hint(true)
             }
             _ => unreachable!(),
         }
     }
 
+    fn set_fullscreen_monitors_hint(&self, top: u32, bottom: u32, left: u32, right: u32) -> util::Flusher {
+        let fullscreen_monitors_atom = unsafe {
+            self.xconn.get_atom_unchecked(b"_NET_WM_FULLSCREEN_MONITORS\0")
+        };
+        self.xconn.send_client_msg(
+            self.xwindow,
+            self.root,
+            fullscreen_monitors_atom,
+            Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+            [top as c_long, bottom as c_long, left as c_long, right as c_long, 1],
+        )
+    }
+
+    pub fn set_fullscreen_monitors(&self, monitors: &[RootMonitorId]) -> Result<(), String> {
+        let monitors: Vec<&X11MonitorId> = monitors
+            .iter()
+            .map(|monitor| match monitor.inner {
+                PlatformMonitorId::X(ref monitor) => monitor,
+                _ => unreachable!(),
+            })
+            .collect();
+        if monitors.is_empty() {
+            self.set_fullscreen(None);
+            return Ok(());
+        }
+
+        let fullscreen_monitors_atom = unsafe {
+            self.xconn.get_atom_unchecked(b"_NET_WM_FULLSCREEN_MONITORS\0")
+        };
+        if !util::hint_is_supported(fullscreen_monitors_atom) {
+            return Err("The window manager doesn't support `_NET_WM_FULLSCREEN_MONITORS`".to_owned());
+        }
+
+        // The WM is told which monitor each edge of the window should line up with.
+        let top = monitors.iter().min_by_key(|monitor| monitor.position.1).unwrap();
+        let bottom = monitors.iter()
+            .max_by_key(|monitor| monitor.position.1 + monitor.dimensions.1 as i32)
+            .unwrap();
+        let left = monitors.iter().min_by_key(|monitor| monitor.position.0).unwrap();
+        let right = monitors.iter()
+            .max_by_key(|monitor| monitor.position.0 + monitor.dimensions.0 as i32)
+            .unwrap();
+        let index = |monitor: &X11MonitorId| {
+            self.xconn.get_xinerama_index(monitor)
+                .ok_or_else(|| format!("Monitor {} doesn't match any Xinerama screen", monitor.name))
+        };
+        let (top, bottom, left, right) = (index(top)?, index(bottom)?, index(left)?, index(right)?);
+
+        self.restore_video_mode();
+        let window_position = self.get_position_physical();
+        {
+            let mut shared_state_lock = self.shared_state.lock();
+            shared_state_lock.restore_position = window_position;
+            shared_state_lock.spans_monitors = true;
+        }
+        let origin = (
+            monitors.iter().map(|monitor| monitor.position.0).min().unwrap(),
+            monitors.iter().map(|monitor| monitor.position.1).min().unwrap(),
+        );
+        self.set_position_inner(origin.0, origin.1).queue();
+        // The monitors have to be set before entering fullscreen for all WMs to pick them up.
+        self.set_fullscreen_monitors_hint(top, bottom, left, right).queue();
+        self.set_fullscreen_hint(true)
+            .flush()
+            .map_err(|err| format!("Failed to change window fullscreen state: {:?}", err))?;
+        self.invalidate_cached_frame_extents();
+        Ok(())
+    }
+
     #[inline]
     pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
+        self.restore_video_mode();
         self.set_fullscreen_inner(monitor)
             .flush()
             .expect("Failed to change window fullscreen state");
         self.invalidate_cached_frame_extents();
     }
 
+    pub fn set_fullscreen_exclusive(&self, monitor: RootMonitorId, video_mode: VideoMode) -> Result<(), String> {
+        let (output, mode) = match monitor.inner {
+            PlatformMonitorId::X(ref monitor) => {
+                let mode = monitor.video_modes
+                    .iter()
+                    .find(|&&(_, supported)| supported == video_mode)
+                    .map(|&(mode, _)| mode)
+                    .ok_or_else(|| "The monitor doesn't support the requested video mode".to_owned())?;
+                (monitor.output, mode)
+            },
+            _ => unreachable!(),
+        };
+        self.restore_video_mode();
+        let previous_mode = self.xconn.set_output_mode(output, mode)?;
+        self.shared_state.lock().restore_video_mode = Some((output, previous_mode));
+        // The monitor's dimensions are about to change, but we'll only hear of it through RandR
+        // events later on.
+        monitor::invalidate_cached_monitor_list();
+        self.set_fullscreen_inner(Some(monitor))
+            .flush()
+            .map_err(|err| format!("Failed to change window fullscreen state: {:?}", err))?;
+        self.invalidate_cached_frame_extents();
+        Ok(())
+    }
+
+    // Switches the monitor back to the mode it had before `set_fullscreen_exclusive`, if needed.
+    pub(crate) fn restore_video_mode(&self) {
+        let restore_video_mode = self.shared_state.lock().restore_video_mode.take();
+        if let Some((output, mode)) = restore_video_mode {
+            if let Err(err) = self.xconn.set_output_mode(output, mode) {
+                warn!("Failed to restore the monitor's video mode: {}", err);
+            }
+            monitor::invalidate_cached_monitor_list();
+        }
+    }
+
     fn get_rect(&self) -> Option<util::AaRect> {
         // TODO: This might round-trip more times than needed.
         if let (Some(position), Some(size)) = (self.get_position_physical(), self.get_outer_size_physical()) {
@@ -585,6 +748,147 @@ impl UnownedWindow {
         self.invalidate_cached_frame_extents();
     }
 
+    fn get_net_wm_state(&self) -> Vec<ffi::Atom> {
+        let state_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+        self.xconn.get_property(
+            self.xwindow,
+            state_atom,
+            ffi::XA_ATOM,
+        ).unwrap_or_else(|_| Vec::with_capacity(0))
+    }
+
+    fn has_net_wm_state(&self, net_wm_state: &[ffi::Atom], hint: &[u8]) -> bool {
+        let hint_atom = unsafe { self.xconn.get_atom_unchecked(hint) };
+        net_wm_state.contains(&hint_atom)
+    }
+
+    fn is_iconic(&self) -> bool {
+        let wm_state_atom = unsafe { self.xconn.get_atom_unchecked(b"WM_STATE\0") };
+        let wm_state: Vec<c_ulong> = self.xconn.get_property(
+            self.xwindow,
+            wm_state_atom,
+            wm_state_atom,
+        ).unwrap_or_else(|_| Vec::with_capacity(0));
+        wm_state.get(0) == Some(&(util::ICONIC_STATE as c_ulong))
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        let net_wm_state = self.get_net_wm_state();
+        self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_MAXIMIZED_HORZ\0")
+            && self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_MAXIMIZED_VERT\0")
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        let net_wm_state = self.get_net_wm_state();
+        // Not every WM sets `_NET_WM_STATE_HIDDEN`, but they all follow ICCCM.
+        self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_HIDDEN\0") || self.is_iconic()
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        let net_wm_state = self.get_net_wm_state();
+        self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_FULLSCREEN\0")
+    }
+
+    pub fn is_focused(&self) -> bool {
+        let mut focus_window = 0;
+        let mut revert_to = 0;
+        unsafe {
+            (self.xconn.xlib.XGetInputFocus)(self.xconn.display, &mut focus_window, &mut revert_to);
+        }
+        self.xconn.check_errors().is_ok() && focus_window == self.xwindow
+    }
+
+    pub fn is_visible(&self) -> bool {
+        let mut attributes: ffi::XWindowAttributes = unsafe { mem::uninitialized() };
+        let status = unsafe {
+            (self.xconn.xlib.XGetWindowAttributes)(self.xconn.display, self.xwindow, &mut attributes)
+        };
+        status != 0
+            && self.xconn.check_errors().is_ok()
+            && attributes.map_state == ffi::IsViewable
+    }
+
+    // Applies what an event told us about the window's state, and returns the new state if it
+    // differs from the one last returned.
+    pub(crate) fn update_window_state(&self, change: WindowStateChange) -> Option<WindowState> {
+        // Properties are only read when they changed, and this must be done *before* locking!
+        let net_wm_state = match change {
+            WindowStateChange::NetWmState => {
+                let net_wm_state = self.get_net_wm_state();
+                Some((
+                    self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_MAXIMIZED_HORZ\0")
+                        && self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_MAXIMIZED_VERT\0"),
+                    self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_FULLSCREEN\0"),
+                    self.has_net_wm_state(&net_wm_state, b"_NET_WM_STATE_HIDDEN\0"),
+                ))
+            },
+            _ => None,
+        };
+        let iconic = match change {
+            WindowStateChange::WmState => Some(self.is_iconic()),
+            _ => None,
+        };
+
+        let mut shared_state_lock = self.shared_state.lock();
+        let mut window_state = shared_state_lock.window_state;
+        if let Some((maximized, fullscreen, hidden)) = net_wm_state {
+            window_state.maximized = maximized;
+            window_state.fullscreen = fullscreen;
+            shared_state_lock.net_wm_hidden = hidden;
+        }
+        if let Some(iconic) = iconic {
+            shared_state_lock.iconic = iconic;
+        }
+        // Not every WM sets `_NET_WM_STATE_HIDDEN`, but they all follow ICCCM.
+        window_state.minimized = shared_state_lock.net_wm_hidden || shared_state_lock.iconic;
+        match change {
+            WindowStateChange::Focused(focused) => window_state.focused = focused,
+            WindowStateChange::Visible(visible) => window_state.visible = visible,
+            WindowStateChange::NetWmState | WindowStateChange::WmState => (),
+        }
+
+        if window_state != shared_state_lock.window_state {
+            shared_state_lock.window_state = window_state;
+            Some(window_state)
+        } else {
+            None
+        }
+    }
+
+    fn set_initial_state_inner(&self, initial_state: c_int) -> util::Flusher {
+        let mut wm_hints = self.xconn.get_wm_hints(self.xwindow).expect("`XGetWMHints` failed");
+        (*wm_hints).flags |= ffi::StateHint;
+        (*wm_hints).initial_state = initial_state;
+        self.xconn.set_wm_hints(self.xwindow, wm_hints)
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        unsafe {
+            if minimized {
+                // This sends the `WM_CHANGE_STATE` client message ICCCM asks for.
+                (self.xconn.xlib.XIconifyWindow)(self.xconn.display, self.xwindow, self.screen_id);
+            } else {
+                // Mapping an iconified window returns it to the normal state.
+                (self.xconn.xlib.XMapRaised)(self.xconn.display, self.xwindow);
+            }
+        }
+        self.xconn.flush_requests().expect("Failed to change window minimization");
+    }
+
+    // Returns whether the window is occluded if that differs from what was last returned. This
+    // relies on the state kept up to date by `update_window_state`.
+    pub(crate) fn update_occluded(&self) -> Option<bool> {
+        let mut shared_state_lock = self.shared_state.lock();
+        let window_state = shared_state_lock.window_state;
+        let occluded = !window_state.visible || window_state.minimized || shared_state_lock.fully_obscured;
+        if util::maybe_change(&mut shared_state_lock.occluded, occluded) {
+            Some(occluded)
+        } else {
+            None
+        }
+    }
+
     fn set_title_inner(&self, title: &str) -> util::Flusher {
         let wm_name_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_NAME\0") };
         let utf8_atom = unsafe { self.xconn.get_atom_unchecked(b"UTF8_STRING\0") };
@@ -649,6 +953,39 @@ impl UnownedWindow {
             .expect("Failed to set always-on-top state");
     }
 
+    fn set_opacity_inner(&self, opacity: f32) -> util::Flusher {
+        let opacity_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_WINDOW_OPACITY\0") };
+        let opacity = opacity.max(0.0).min(1.0);
+        if opacity < 1.0 {
+            let opacity = (opacity as f64 * c_uint::max_value() as f64) as util::Cardinal;
+            self.xconn.change_property(
+                self.xwindow,
+                opacity_atom,
+                ffi::XA_CARDINAL,
+                util::PropMode::Replace,
+                &[opacity],
+            )
+        } else {
+            // Compositors treat a missing property as opaque, which spares them from blending.
+            unsafe {
+                (self.xconn.xlib.XDeleteProperty)(self.xconn.display, self.xwindow, opacity_atom);
+            }
+            util::Flusher::new(&self.xconn)
+        }
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        self.set_opacity_inner(opacity)
+            .flush()
+            .expect("Failed to set window opacity");
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        self.set_urgent(request_type.is_some());
+    }
+
     fn set_icon_inner(&self, icon: Icon) -> util::Flusher {
         let icon_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_ICON\0") };
         let data = icon.to_cardinals();
@@ -699,6 +1036,38 @@ impl UnownedWindow {
         }
     }
 
+    pub fn focus(&self) {
+        let active_window_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_ACTIVE_WINDOW\0") };
+        if util::hint_is_supported(active_window_atom) {
+            self.xconn.send_client_msg(
+                self.xwindow,
+                self.root,
+                active_window_atom,
+                Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+                [
+                    1, // source indication: a normal application
+                    ffi::CurrentTime as c_long,
+                    0, // the window we think is currently active, which we don't know
+                    0,
+                    0,
+                ],
+            ).flush().expect("Failed to send client message");
+        } else {
+            unsafe {
+                (self.xconn.xlib.XMapRaised)(self.xconn.display, self.xwindow);
+                (self.xconn.xlib.XSetInputFocus)(
+                    self.xconn.display,
+                    self.xwindow,
+                    ffi::RevertToParent,
+                    ffi::CurrentTime,
+                );
+            }
+            // `XSetInputFocus` fails if the window hasn't been mapped yet, which isn't worth
+            // panicking over.
+            let _ = self.xconn.flush_requests();
+        }
+    }
+
     fn update_cached_frame_extents(&self) {
         let extents = self.xconn.get_frame_extents_heuristic(self.xwindow, self.root);
         (*self.shared_state.lock()).frame_extents = Some(extents);
@@ -1152,6 +1521,199 @@ impl UnownedWindow {
         result
     }
 
+    #[inline]
+    pub fn grab_keyboard(&self, grab: bool) -> Result<(), String> {
+        let mut grabbed_lock = self.keyboard_grabbed.lock();
+        if grab == *grabbed_lock { return Ok(()); }
+        let result = if grab {
+            let result = unsafe {
+                (self.xconn.xlib.XGrabKeyboard)(
+                    self.xconn.display,
+                    self.xwindow,
+                    ffi::True,
+                    ffi::GrabModeAsync,
+                    ffi::GrabModeAsync,
+                    ffi::CurrentTime,
+                )
+            };
+
+            match result {
+                ffi::GrabSuccess => Ok(()),
+                ffi::AlreadyGrabbed => Err("Keyboard could not be grabbed: already grabbed by another client"),
+                ffi::GrabInvalidTime => Err("Keyboard could not be grabbed: invalid time"),
+                ffi::GrabNotViewable => Err("Keyboard could not be grabbed: grab location not viewable"),
+                ffi::GrabFrozen => Err("Keyboard could not be grabbed: frozen by another client"),
+                _ => unreachable!(),
+            }.map_err(|err| err.to_owned())
+        } else {
+            unsafe {
+                (self.xconn.xlib.XUngrabKeyboard)(self.xconn.display, ffi::CurrentTime);
+            }
+            self.xconn.flush_requests()
+                .map_err(|err| format!("Failed to call `XUngrabKeyboard`: {:?}", err))
+        };
+        if result.is_ok() {
+            *grabbed_lock = grab;
+        }
+        result
+    }
+
+    fn drag_initiate(&self, action: c_long) -> Result<(), String> {
+        let moveresize_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_MOVERESIZE\0") };
+        if !util::hint_is_supported(moveresize_atom) {
+            return Err("The window manager doesn't support `_NET_WM_MOVERESIZE`".to_owned());
+        }
+
+        let pointer = self.xconn
+            .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| format!("Failed to query pointer: {:?}", err))?;
+        let (root_x, root_y) = (pointer.root_x as c_long, pointer.root_y as c_long);
+
+        // The WM has to grab the pointer itself, so we release our grab along with the implicit
+        // one from the button press. `grab_cursor` has to be called again to get it back.
+        *self.cursor_grabbed.lock() = false;
+        unsafe {
+            (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+        }
+        self.xconn.send_client_msg(
+            self.xwindow,
+            self.root,
+            moveresize_atom,
+            Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+            [
+                root_x,
+                root_y,
+                action,
+                1, // the left mouse button
+                1, // source indication: a normal application
+            ],
+        ).flush().map_err(|err| format!("Failed to send `_NET_WM_MOVERESIZE`: {:?}", err))
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), String> {
+        self.drag_initiate(util::MOVERESIZE_MOVE)
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), String> {
+        self.drag_initiate(match direction {
+            ResizeDirection::East => util::MOVERESIZE_RIGHT,
+            ResizeDirection::North => util::MOVERESIZE_TOP,
+            ResizeDirection::NorthEast => util::MOVERESIZE_TOPRIGHT,
+            ResizeDirection::NorthWest => util::MOVERESIZE_TOPLEFT,
+            ResizeDirection::South => util::MOVERESIZE_BOTTOM,
+            ResizeDirection::SouthEast => util::MOVERESIZE_BOTTOMRIGHT,
+            ResizeDirection::SouthWest => util::MOVERESIZE_BOTTOMLEFT,
+            ResizeDirection::West => util::MOVERESIZE_LEFT,
+        })
+    }
+
+    #[inline]
+    pub fn set_hit_test(&self, hit_test: Option<Box<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>) {
+        *self.hit_test.lock() = hit_test.map(Arc::from);
+    }
+
+    pub fn show_window_menu(&self, logical_position: LogicalPosition) -> Result<(), String> {
+        let show_window_menu_atom = unsafe { self.xconn.get_atom_unchecked(b"_GTK_SHOW_WINDOW_MENU\0") };
+        if !util::hint_is_supported(show_window_menu_atom) {
+            return Err("The window manager doesn't support `_GTK_SHOW_WINDOW_MENU`".to_owned());
+        }
+
+        let (x, y): (i32, i32) = logical_position.to_physical(self.get_hidpi_factor()).into();
+        let (inner_x, inner_y) = self.get_inner_position_physical()
+            .ok_or_else(|| "Failed to get the window's position".to_owned())?;
+
+        // The WM grabs the pointer while the menu is open.
+        *self.cursor_grabbed.lock() = false;
+        unsafe {
+            (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+        }
+        self.xconn.send_client_msg(
+            self.xwindow,
+            self.root,
+            show_window_menu_atom,
+            Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+            [
+                util::VIRTUAL_CORE_POINTER as c_long,
+                (inner_x + x) as c_long,
+                (inner_y + y) as c_long,
+                0,
+                0,
+            ],
+        ).flush().map_err(|err| format!("Failed to send `_GTK_SHOW_WINDOW_MENU`: {:?}", err))
+    }
+
+    pub fn set_input_region(&self, region: Option<Vec<Rect>>) -> Result<(), String> {
+        let dpi_factor = self.get_hidpi_factor();
+        let mut rectangles: Option<Vec<ffi::XRectangle>> = region.map(|region| {
+            region
+                .into_iter()
+                .map(|rect| {
+                    let (x, y): (i32, i32) = rect.position.to_physical(dpi_factor).into();
+                    let (width, height): (u32, u32) = rect.size.to_physical(dpi_factor).into();
+                    // Clamp to the range of `XRectangle`'s fields, rather than letting casting wrap
+                    let clamp_coord = |coord: i32| coord
+                        .max(c_short::min_value() as i32)
+                        .min(c_short::max_value() as i32) as c_short;
+                    let clamp_dimension = |dimension: u32| dimension
+                        .min(c_ushort::max_value() as u32) as c_ushort;
+                    ffi::XRectangle {
+                        x: clamp_coord(x),
+                        y: clamp_coord(y),
+                        width: clamp_dimension(width),
+                        height: clamp_dimension(height),
+                    }
+                })
+                .collect()
+        });
+        self.xconn.set_input_shape(
+            self.xwindow,
+            rectangles.as_mut().map(|rectangles| &mut rectangles[..]),
+        )
+    }
+
+    // Acts on a left mouse button press at `position`, which is in physical coordinates relative
+    // to the window, according to the hit test function.
+    pub(crate) fn hit_test(&self, position: (f64, f64)) -> HitTestResult {
+        // The lock is released before calling into the application.
+        let hit_test = self.hit_test.lock().clone();
+        let result = match hit_test {
+            Some(hit_test) => hit_test(self.logicalize_position(position)),
+            None => HitTestResult::Client,
+        };
+        let action = match result {
+            HitTestResult::Caption => self.drag_window(),
+            HitTestResult::Resize(direction) => self.drag_resize_window(direction),
+            HitTestResult::Minimize => {
+                self.set_minimized(true);
+                Ok(())
+            },
+            HitTestResult::Maximize => {
+                let maximized = self.is_maximized();
+                self.set_maximized(!maximized);
+                Ok(())
+            },
+            HitTestResult::Client | HitTestResult::Close => Ok(()),
+        };
+        // If the WM can't move or resize the window, the press is delivered as usual instead.
+        let result = match action {
+            Ok(()) => result,
+            Err(err) => {
+                warn!("{}", err);
+                HitTestResult::Client
+            },
+        };
+        *self.hit_test_consumed_press.lock() = result != HitTestResult::Client;
+        result
+    }
+
+    // Whether a left mouse button release belongs to a press consumed by the hit test, in which
+    // case it shouldn't be delivered either.
+    pub(crate) fn take_hit_test_consumed_press(&self) -> bool {
+        mem::replace(&mut *self.hit_test_consumed_press.lock(), false)
+    }
+
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
         let mut hidden_lock = self.cursor_hidden.lock();
@@ -16,6 +16,8 @@ pub struct XConnection {
     pub xrandr_1_5: Option<ffi::Xrandr>,
     pub xcursor: ffi::Xcursor,
     pub xinput2: ffi::XInput2,
+    pub xinerama: Option<ffi::Xinerama>,
+    pub xfixes: Option<ffi::XFixes>,
     pub xlib_xcb: ffi::Xlib_xcb,
     pub display: *mut ffi::Display,
     pub latest_error: Mutex<Option<XError>>,
@@ -34,6 +36,8 @@ impl XConnection {
         let xrandr = ffi::Xrandr_2_2_0::open()?;
         let xrandr_1_5 = ffi::Xrandr::open().ok();
         let xinput2 = ffi::XInput2::open()?;
+        let xinerama = ffi::Xinerama::open().ok();
+        let xfixes = ffi::XFixes::open().ok();
         let xlib_xcb = ffi::Xlib_xcb::open()?;
 
         unsafe { (xlib.XInitThreads)() };
@@ -48,12 +52,27 @@ impl XConnection {
             display
         };
 
+        // XFixes requests are only valid once we've told the server which version we speak.
+        let xfixes = xfixes.and_then(|xfixes| {
+            let mut major = 5;
+            let mut minor = 0;
+            let supported = unsafe { (xfixes.XFixesQueryVersion)(display, &mut major, &mut minor) };
+            // Regions need at least version 2.
+            if supported != 0 && major >= 2 {
+                Some(xfixes)
+            } else {
+                None
+            }
+        });
+
         Ok(XConnection {
             xlib,
             xrandr,
             xrandr_1_5,
             xcursor,
             xinput2,
+            xinerama,
+            xfixes,
             xlib_xcb,
             display,
             latest_error: Mutex::new(None),
@@ -0,0 +1,213 @@
+// Bindings to the few libxkbcommon functions we need to inspect keymaps. The library is loaded at
+// runtime, as smithay client toolkit does, so that it stays optional.
+
+use std::ffi::CStr;
+use std::mem;
+use std::os::raw::{c_char, c_int, c_void};
+use std::os::unix::io::RawFd;
+use std::{ptr, slice};
+
+use libc;
+
+use super::dlopen;
+
+pub const XKB_KEYMAP_FORMAT_TEXT_V1: c_int = 1;
+pub const XKB_STATE_LAYOUT_EFFECTIVE: c_int = 1 << 7;
+
+pub struct XkbCommon {
+    pub context_new: unsafe extern "C" fn(c_int) -> *mut c_void,
+    pub context_unref: unsafe extern "C" fn(*mut c_void),
+    pub keymap_new_from_buffer: unsafe extern "C" fn(*mut c_void, *const c_char, usize, c_int, c_int) -> *mut c_void,
+    pub keymap_unref: unsafe extern "C" fn(*mut c_void),
+    pub keymap_layout_get_name: unsafe extern "C" fn(*mut c_void, u32) -> *const c_char,
+    pub state_new: unsafe extern "C" fn(*mut c_void) -> *mut c_void,
+    pub state_unref: unsafe extern "C" fn(*mut c_void),
+    pub state_update_mask: unsafe extern "C" fn(*mut c_void, u32, u32, u32, u32, u32, u32) -> c_int,
+    pub state_serialize_layout: unsafe extern "C" fn(*mut c_void, c_int) -> u32,
+    pub state_key_get_utf8: unsafe extern "C" fn(*mut c_void, u32, *mut c_char, usize) -> c_int,
+    pub keysym_to_utf8: unsafe extern "C" fn(u32, *mut c_char, usize) -> c_int,
+}
+
+unsafe fn load_symbol(lib: *mut c_void, name: &[u8]) -> Option<*mut c_void> {
+    let symbol = dlopen::dlsym(lib, name.as_ptr() as *const c_char);
+    if symbol.is_null() {
+        None
+    } else {
+        Some(symbol)
+    }
+}
+
+impl XkbCommon {
+    unsafe fn open() -> Option<XkbCommon> {
+        let lib = dlopen::dlopen(b"libxkbcommon.so.0\0".as_ptr() as *const c_char, dlopen::RTLD_LAZY);
+        if lib.is_null() {
+            return None;
+        }
+        Some(XkbCommon {
+            context_new: mem::transmute(load_symbol(lib, b"xkb_context_new\0")?),
+            context_unref: mem::transmute(load_symbol(lib, b"xkb_context_unref\0")?),
+            keymap_new_from_buffer: mem::transmute(load_symbol(lib, b"xkb_keymap_new_from_buffer\0")?),
+            keymap_unref: mem::transmute(load_symbol(lib, b"xkb_keymap_unref\0")?),
+            keymap_layout_get_name: mem::transmute(load_symbol(lib, b"xkb_keymap_layout_get_name\0")?),
+            state_new: mem::transmute(load_symbol(lib, b"xkb_state_new\0")?),
+            state_unref: mem::transmute(load_symbol(lib, b"xkb_state_unref\0")?),
+            state_update_mask: mem::transmute(load_symbol(lib, b"xkb_state_update_mask\0")?),
+            state_serialize_layout: mem::transmute(load_symbol(lib, b"xkb_state_serialize_layout\0")?),
+            state_key_get_utf8: mem::transmute(load_symbol(lib, b"xkb_state_key_get_utf8\0")?),
+            keysym_to_utf8: mem::transmute(load_symbol(lib, b"xkb_keysym_to_utf8\0")?),
+        })
+    }
+}
+
+unsafe impl Send for XkbCommon {}
+unsafe impl Sync for XkbCommon {}
+
+lazy_static! {
+    pub static ref XKBCOMMON: Option<XkbCommon> = unsafe { XkbCommon::open() };
+}
+
+// Keys such as Enter or Escape produce control characters, which make for poor labels.
+pub fn label_from_utf8(buffer: &[u8]) -> Option<String> {
+    let label = String::from_utf8_lossy(buffer).into_owned();
+    if label.is_empty() || label.chars().any(char::is_control) {
+        None
+    } else {
+        Some(label)
+    }
+}
+
+/// Converts a keysym to the text it produces.
+pub fn keysym_to_label(keysym: u32) -> Option<String> {
+    if let Some(ref xkb) = *XKBCOMMON {
+        let mut buffer = [0u8; 8];
+        let written = unsafe { (xkb.keysym_to_utf8)(keysym, buffer.as_mut_ptr() as *mut c_char, buffer.len()) };
+        if written <= 1 {
+            return None;
+        }
+        // `written` includes the terminating NUL.
+        return label_from_utf8(&buffer[..written as usize - 1]);
+    }
+    // Without libxkbcommon, we can still handle Latin-1 keysyms, which match their code points,
+    // and Unicode keysyms.
+    let codepoint = match keysym {
+        0x20..=0x7e | 0xa0..=0xff => keysym,
+        0x0100_0100..=0x0110_ffff => keysym - 0x0100_0000,
+        _ => return None,
+    };
+    ::std::char::from_u32(codepoint)
+        .and_then(|chr| label_from_utf8(chr.to_string().as_bytes()))
+}
+
+/// The keymap sent by the compositor through `wl_keyboard.keymap`, along with the active layout.
+pub struct Keymap {
+    xkb: &'static XkbCommon,
+    keymap: *mut c_void,
+    state: *mut c_void,
+}
+
+// The keymap is only ever accessed behind a `Mutex`, and libxkbcommon objects aren't tied to a thread.
+unsafe impl Send for Keymap {}
+
+impl Keymap {
+    /// Loads a `XkbV1` keymap from the file descriptor sent by the compositor.
+    ///
+    /// Returns `None` if libxkbcommon is unavailable or the keymap couldn't be parsed.
+    pub fn from_fd(fd: RawFd, size: u32) -> Option<Keymap> {
+        let xkb = XKBCOMMON.as_ref()?;
+        unsafe {
+            let map = libc::mmap(
+                ptr::null_mut(),
+                size as usize,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                fd,
+                0,
+            );
+            if map == libc::MAP_FAILED {
+                return None;
+            }
+            let context = (xkb.context_new)(0);
+            let keymap = if context.is_null() {
+                ptr::null_mut()
+            } else {
+                // The keymap is NUL-terminated, which libxkbcommon doesn't want to see.
+                let bytes = slice::from_raw_parts(map as *const u8, size as usize);
+                let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                let keymap = (xkb.keymap_new_from_buffer)(
+                    context,
+                    map as *const c_char,
+                    len,
+                    XKB_KEYMAP_FORMAT_TEXT_V1,
+                    0,
+                );
+                (xkb.context_unref)(context);
+                keymap
+            };
+            libc::munmap(map, size as usize);
+            if keymap.is_null() {
+                return None;
+            }
+            let state = (xkb.state_new)(keymap);
+            if state.is_null() {
+                (xkb.keymap_unref)(keymap);
+                return None;
+            }
+            Some(Keymap { xkb, keymap, state })
+        }
+    }
+
+    /// Updates the active layout from a `wl_keyboard.modifiers` event.
+    ///
+    /// Returns `true` if the active layout changed.
+    pub fn update_group(&mut self, group: u32) -> bool {
+        let previous = self.group();
+        unsafe {
+            (self.xkb.state_update_mask)(self.state, 0, 0, 0, 0, 0, group);
+        }
+        self.group() != previous
+    }
+
+    /// The index of the layout currently in effect.
+    pub fn group(&self) -> u32 {
+        unsafe { (self.xkb.state_serialize_layout)(self.state, XKB_STATE_LAYOUT_EFFECTIVE) }
+    }
+
+    /// The name of the layout currently in effect, e.g. "English (US)".
+    pub fn layout_name(&self) -> Option<String> {
+        unsafe {
+            let name = (self.xkb.keymap_layout_get_name)(self.keymap, self.group());
+            if name.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    /// The text produced by a key in the active layout, without any modifiers held.
+    pub fn key_label(&self, scancode: u32) -> Option<String> {
+        let mut buffer = [0u8; 16];
+        let len = unsafe {
+            (self.xkb.state_key_get_utf8)(
+                self.state,
+                // XKB keycodes are evdev scancodes offset by 8.
+                scancode + 8,
+                buffer.as_mut_ptr() as *mut c_char,
+                buffer.len(),
+            )
+        };
+        if len <= 0 || len as usize >= buffer.len() {
+            return None;
+        }
+        label_from_utf8(&buffer[..len as usize])
+    }
+}
+
+impl Drop for Keymap {
+    fn drop(&mut self) {
+        unsafe {
+            (self.xkb.state_unref)(self.state);
+            (self.xkb.keymap_unref)(self.keymap);
+        }
+    }
+}
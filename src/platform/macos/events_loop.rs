@@ -518,6 +518,18 @@ impl EventsLoop {
         Proxy {}
     }
 
+    pub fn get_pressed_keys(&self) -> Vec<::VirtualKeyCode> {
+        Vec::new()
+    }
+
+    pub fn get_pressed_mouse_buttons(&self) -> Vec<::MouseButton> {
+        Vec::new()
+    }
+
+    pub fn get_cursor_position(&self) -> Option<::PhysicalPosition> {
+        None
+    }
+
 }
 
 impl Proxy {
@@ -728,10 +740,13 @@ unsafe fn modifier_event(
             device_id: DEVICE_ID,
             input: KeyboardInput {
                 state,
+                is_repeat: false,
                 scancode,
                 virtual_keycode,
+                physical_key: None,
                 modifiers: event_mods(ns_event),
             },
+            is_synthetic: false,
         })
     } else {
         None
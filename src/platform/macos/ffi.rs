@@ -105,3 +105,8 @@ pub enum NSWindowLevel {
     NSPopUpMenuWindowLevel = kCGPopUpMenuWindowLevelKey as _,
     NSScreenSaverWindowLevel = kCGScreenSaverWindowLevelKey as _,
 }
+
+pub enum NSRequestUserAttentionType {
+    NSCriticalRequest = 0,
+    NSInformationalRequest = 10,
+}
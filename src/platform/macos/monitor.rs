@@ -6,7 +6,7 @@ use cocoa::base::{id, nil};
 use cocoa::foundation::{NSString, NSUInteger};
 use core_graphics::display::{CGDirectDisplayID, CGDisplay, CGDisplayBounds};
 
-use {PhysicalPosition, PhysicalSize};
+use {GammaRamp, MonitorTransform, PhysicalPosition, PhysicalSize, SubpixelLayout, VideoMode};
 use super::EventsLoop;
 use super::window::{IdRef, Window2};
 
@@ -122,6 +122,66 @@ impl MonitorId {
         unsafe { NSScreen::backingScaleFactor(screen) as f64 }
     }
 
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial_number(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_persistent_id(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> (PhysicalPosition, PhysicalSize) {
+        (self.get_position(), self.get_dimensions())
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &GammaRamp) -> Result<(), String> {
+        Err("Changing the gamma ramp is not yet possible on macOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline]
+    pub fn get_physical_dimensions(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_layout(&self) -> SubpixelLayout {
+        SubpixelLayout::Unknown
+    }
+
     pub(crate) fn get_nsscreen(&self) -> Option<id> {
         unsafe {
             let native_id = self.get_native_identifier();
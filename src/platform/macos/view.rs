@@ -382,10 +382,13 @@ extern fn key_down(this: &Object, _sel: Sel, event: id) {
                 device_id: DEVICE_ID,
                 input: KeyboardInput {
                     state: ElementState::Pressed,
+                    is_repeat,
                     scancode,
                     virtual_keycode,
+                    physical_key: None,
                     modifiers: event_mods(event),
                 },
+                is_synthetic: false,
             },
         };
 
@@ -442,10 +445,13 @@ extern fn key_up(this: &Object, _sel: Sel, event: id) {
                 device_id: DEVICE_ID,
                 input: KeyboardInput {
                     state: ElementState::Released,
+                    is_repeat: false,
                     scancode,
                     virtual_keycode,
+                    physical_key: None,
                     modifiers: event_mods(event),
                 },
+                is_synthetic: false,
             },
         };
 
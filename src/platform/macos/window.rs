@@ -18,7 +18,7 @@ use cocoa::appkit::{
     NSWindowStyleMask,
 };
 use cocoa::base::{id, nil};
-use cocoa::foundation::{NSAutoreleasePool, NSDictionary, NSPoint, NSRect, NSSize, NSString};
+use cocoa::foundation::{NSAutoreleasePool, NSDictionary, NSInteger, NSPoint, NSRect, NSSize, NSString};
 
 use core_graphics::display::CGDisplay;
 
@@ -29,9 +29,14 @@ use objc::declare::ClassDecl;
 use {
     CreationError,
     Event,
+    HitTestResult,
     LogicalPosition,
     LogicalSize,
     MouseCursor,
+    Rect,
+    ResizeDirection,
+    UserAttentionType,
+    VideoMode,
     WindowAttributes,
     WindowEvent,
     WindowId,
@@ -703,6 +708,14 @@ impl Window2 {
             window.delegate.state.perform_maximized(win_attribs.maximized);
         }
 
+        if win_attribs.minimized {
+            window.set_minimized(true);
+        }
+
+        if win_attribs.opacity < 1.0 {
+            window.set_opacity(win_attribs.opacity);
+        }
+
         let _: () = unsafe { msg_send![autoreleasepool, drain] };
 
         Ok(window)
@@ -875,6 +888,14 @@ impl Window2 {
         unsafe { NSWindow::orderOut_(*self.window, nil); }
     }
 
+    #[inline]
+    pub fn focus(&self) {
+        unsafe {
+            appkit::NSApp().activateIgnoringOtherApps_(YES);
+            NSWindow::makeKeyAndOrderFront_(*self.window, nil);
+        }
+    }
+
     pub fn get_position(&self) -> Option<LogicalPosition> {
         let frame_rect = unsafe { NSWindow::frame(*self.window) };
         Some((
@@ -997,6 +1018,53 @@ impl Window2 {
         }
     }
 
+    #[inline]
+    pub fn grab_keyboard(&self, _grab: bool) -> Result<(), String> {
+        Err("Keyboard grabbing is not yet possible on macOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), String> {
+        unsafe {
+            let event: id = msg_send![appkit::NSApp(), currentEvent];
+            if event == nil {
+                return Err("There is no mouse event to start moving the window from.".to_owned());
+            }
+            let _: () = msg_send![*self.window, performWindowDragWithEvent:event];
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), String> {
+        Err("Resizing the window is not yet possible on macOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_hit_test(&self, _hit_test: Option<Box<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>) {
+        // TODO
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: LogicalPosition) -> Result<(), String> {
+        Err("Showing the window menu is not yet possible on macOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_input_region(&self, _region: Option<Vec<Rect>>) -> Result<(), String> {
+        Err("Changing the input region is not yet possible on macOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_fullscreen_exclusive(&self, _monitor: RootMonitorId, _video_mode: VideoMode) -> Result<(), String> {
+        Err("Changing the video mode is not yet possible on macOS.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_fullscreen_monitors(&self, _monitors: &[RootMonitorId]) -> Result<(), String> {
+        Err("Spanning fullscreen across monitors is not yet possible on macOS.".to_owned())
+    }
+
     #[inline]
     pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
         // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
@@ -1042,11 +1110,47 @@ impl Window2 {
         Ok(())
     }
 
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         self.delegate.state.perform_maximized(maximized)
     }
 
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        unsafe {
+            if minimized {
+                let _: () = msg_send![*self.window, miniaturize:nil];
+            } else {
+                let _: () = msg_send![*self.window, deminiaturize:nil];
+            }
+        }
+    }
+
     #[inline]
     /// TODO: Right now set_fullscreen do not work on switching monitors
     /// in fullscreen mode
@@ -1135,6 +1239,26 @@ impl Window2 {
         }
     }
 
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        let opacity = opacity.max(0.0).min(1.0) as CGFloat;
+        unsafe {
+            let _: () = msg_send![*self.window, setAlphaValue:opacity];
+        }
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        let request_type = match request_type {
+            Some(UserAttentionType::Critical) => ffi::NSRequestUserAttentionType::NSCriticalRequest,
+            Some(UserAttentionType::Informational) => ffi::NSRequestUserAttentionType::NSInformationalRequest,
+            None => return,
+        };
+        unsafe {
+            let _: NSInteger = msg_send![appkit::NSApp(), requestUserAttention:request_type as NSInteger];
+        }
+    }
+
     #[inline]
     pub fn set_window_icon(&self, _icon: Option<::Icon>) {
         // macOS doesn't have window icons. Though, there is `setRepresentedFilename`, but that's
@@ -28,6 +28,12 @@ pub fn get_key_mods() -> ModifiersState {
     mods
 }
 
+// Unlike `GetKeyState`, `GetAsyncKeyState` reflects the state of the key right now, even if another application has
+// focus.
+pub fn is_vkey_down(vkey: c_int) -> bool {
+    unsafe { winuser::GetAsyncKeyState(vkey) & (1 << 15) == (1 << 15) }
+}
+
 pub fn vkey_to_winit_vkey(vkey: c_int) -> Option<VirtualKeyCode> {
     // VK_* codes are documented here https://msdn.microsoft.com/en-us/library/windows/desktop/dd375731(v=vs.85).aspx
     match vkey {
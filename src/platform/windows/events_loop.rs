@@ -42,10 +42,15 @@ use {
     ControlFlow,
     Event,
     EventsLoopClosed,
+    HitTestResult,
     KeyboardInput,
     LogicalPosition,
     LogicalSize,
+    MouseButton,
+    PhysicalPosition,
     PhysicalSize,
+    ResizeDirection,
+    VirtualKeyCode,
     WindowEvent,
     WindowId as SuperWindowId,
 };
@@ -57,7 +62,7 @@ use platform::platform::dpi::{
     enable_non_client_dpi_scaling,
     get_hwnd_scale_factor,
 };
-use platform::platform::event::{handle_extended_keys, process_key_params, vkey_to_winit_vkey};
+use platform::platform::event::{handle_extended_keys, is_vkey_down, process_key_params, vkey_to_winit_vkey};
 use platform::platform::icon::WinIcon;
 use platform::platform::raw_input::{get_raw_input_data, get_raw_mouse_button_state};
 use platform::platform::window::adjust_size;
@@ -102,6 +107,8 @@ pub struct WindowState {
     pub always_on_top: bool,
     pub maximized: bool,
     pub resizable: bool,
+    /// Used by `WM_NCHITTEST`.
+    pub hit_test: Option<Arc<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>,
 }
 
 impl WindowState {
@@ -252,6 +259,36 @@ impl EventsLoop {
         }
     }
 
+    pub fn get_pressed_keys(&self) -> Vec<VirtualKeyCode> {
+        (0..256)
+            .filter(|&vkey| is_vkey_down(vkey))
+            .filter_map(vkey_to_winit_vkey)
+            .collect()
+    }
+
+    pub fn get_pressed_mouse_buttons(&self) -> Vec<MouseButton> {
+        let buttons = [
+            (winuser::VK_LBUTTON, MouseButton::Left),
+            (winuser::VK_RBUTTON, MouseButton::Right),
+            (winuser::VK_MBUTTON, MouseButton::Middle),
+            (winuser::VK_XBUTTON1, MouseButton::Other(1)),
+            (winuser::VK_XBUTTON2, MouseButton::Other(2)),
+        ];
+        buttons
+            .iter()
+            .filter(|&&(vkey, _)| is_vkey_down(vkey))
+            .map(|&(_, button)| button)
+            .collect()
+    }
+
+    pub fn get_cursor_position(&self) -> Option<PhysicalPosition> {
+        let mut point = POINT { x: 0, y: 0 };
+        if unsafe { winuser::GetCursorPos(&mut point) } == 0 {
+            return None;
+        }
+        Some((point.x, point.y).into())
+    }
+
     /// Executes a function in the background thread.
     ///
     /// Note that we use a FnMut instead of a FnOnce because we're too lazy to create an equivalent
@@ -623,10 +660,15 @@ pub unsafe extern "system" fn callback(
                             device_id: DEVICE_ID,
                             input: KeyboardInput {
                                 state: Pressed,
+                                // Bit 30 of `lparam` is the previous key state, which is set for auto-repeated
+                                // `WM_KEYDOWN` messages.
+                                is_repeat: lparam & (1 << 30) != 0,
                                 scancode: scancode,
                                 virtual_keycode: vkey,
+                                physical_key: None,
                                 modifiers: event::get_key_mods(),
-                            }
+                            },
+                            is_synthetic: false,
                         }
                     });
                     // Windows doesn't emit a delete character by default, but in order to make it
@@ -651,10 +693,13 @@ pub unsafe extern "system" fn callback(
                         device_id: DEVICE_ID,
                         input: KeyboardInput {
                             state: Released,
+                            is_repeat: false,
                             scancode: scancode,
                             virtual_keycode: vkey,
+                            physical_key: None,
                             modifiers: event::get_key_mods(),
                         },
+                        is_synthetic: false,
                     }
                 });
             }
@@ -895,7 +940,9 @@ pub unsafe extern "system" fn callback(
                                 event: Key(KeyboardInput {
                                     scancode,
                                     state,
+                                    is_repeat: false,
                                     virtual_keycode,
+                                    physical_key: None,
                                     modifiers: event::get_key_mods(),
                                 }),
                             });
@@ -1005,6 +1052,51 @@ pub unsafe extern "system" fn callback(
             }
         },
 
+        winuser::WM_NCHITTEST => {
+            let hit_test = CONTEXT_STASH.with(|context_stash| {
+                context_stash
+                    .borrow()
+                    .as_ref()
+                    .and_then(|cstash| cstash.windows.get(&window))
+                    .and_then(|window_state_mutex| {
+                        let window_state = window_state_mutex.lock().unwrap();
+                        window_state.hit_test
+                            .clone()
+                            .map(|hit_test| (hit_test, window_state.dpi_factor))
+                    })
+            });
+
+            match hit_test {
+                Some((hit_test, dpi_factor)) => {
+                    // The position is in screen coordinates.
+                    let mut point = POINT {
+                        x: windowsx::GET_X_LPARAM(lparam),
+                        y: windowsx::GET_Y_LPARAM(lparam),
+                    };
+                    winuser::ScreenToClient(window, &mut point);
+                    let position = LogicalPosition::from_physical((point.x as f64, point.y as f64), dpi_factor);
+                    match hit_test(position) {
+                        HitTestResult::Client => winuser::HTCLIENT,
+                        HitTestResult::Caption => winuser::HTCAPTION,
+                        HitTestResult::Resize(direction) => match direction {
+                            ResizeDirection::East => winuser::HTRIGHT,
+                            ResizeDirection::North => winuser::HTTOP,
+                            ResizeDirection::NorthEast => winuser::HTTOPRIGHT,
+                            ResizeDirection::NorthWest => winuser::HTTOPLEFT,
+                            ResizeDirection::South => winuser::HTBOTTOM,
+                            ResizeDirection::SouthEast => winuser::HTBOTTOMRIGHT,
+                            ResizeDirection::SouthWest => winuser::HTBOTTOMLEFT,
+                            ResizeDirection::West => winuser::HTLEFT,
+                        },
+                        HitTestResult::Minimize => winuser::HTMINBUTTON,
+                        HitTestResult::Maximize => winuser::HTMAXBUTTON,
+                        HitTestResult::Close => winuser::HTCLOSE,
+                    }
+                },
+                None => winuser::DefWindowProcW(window, msg, wparam, lparam),
+            }
+        },
+
         winuser::WM_DROPFILES => {
             use events::WindowEvent::DroppedFile;
 
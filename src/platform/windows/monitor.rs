@@ -8,6 +8,7 @@ use std::collections::VecDeque;
 
 use super::{EventsLoop, util};
 use dpi::{PhysicalPosition, PhysicalSize};
+use {GammaRamp, MonitorTransform, SubpixelLayout, VideoMode};
 use platform::platform::dpi::{dpi_to_scale_factor, get_monitor_dpi};
 use platform::platform::window::Window;
 
@@ -170,4 +171,72 @@ impl MonitorId {
     pub fn get_hidpi_factor(&self) -> f64 {
         self.hidpi_factor
     }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial_number(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_persistent_id(&self) -> Option<String> {
+        None
+    }
+
+    pub fn get_work_area(&self) -> (PhysicalPosition, PhysicalSize) {
+        match get_monitor_info(self.hmonitor.0) {
+            Ok(monitor_info) => {
+                let work = monitor_info.rcWork;
+                (
+                    (work.left as i32, work.top as i32).into(),
+                    ((work.right - work.left) as u32, (work.bottom - work.top) as u32).into(),
+                )
+            },
+            Err(_) => (self.get_position(), self.get_dimensions()),
+        }
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &GammaRamp) -> Result<(), String> {
+        Err("Changing the gamma ramp is not yet possible on Windows.".to_owned())
+    }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline]
+    pub fn get_physical_dimensions(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_layout(&self) -> SubpixelLayout {
+        SubpixelLayout::Unknown
+    }
 }
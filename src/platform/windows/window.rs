@@ -8,7 +8,7 @@ use std::sync::{Arc, Mutex};
 use std::sync::mpsc::channel;
 
 use winapi::ctypes::c_int;
-use winapi::shared::minwindef::{BOOL, DWORD, FALSE, LPARAM, TRUE, UINT, WORD, WPARAM};
+use winapi::shared::minwindef::{BOOL, BYTE, DWORD, FALSE, LPARAM, TRUE, UINT, WORD, WPARAM};
 use winapi::shared::windef::{HWND, LPPOINT, POINT, RECT};
 use winapi::um::{combaseapi, dwmapi, libloaderapi, winuser};
 use winapi::um::objbase::COINIT_MULTITHREADED;
@@ -17,12 +17,17 @@ use winapi::um::winnt::{LONG, LPCWSTR};
 
 use {
     CreationError,
+    HitTestResult,
     Icon,
     LogicalPosition,
     LogicalSize,
     MonitorId as RootMonitorId,
     MouseCursor,
     PhysicalSize,
+    Rect,
+    ResizeDirection,
+    UserAttentionType,
+    VideoMode,
     WindowAttributes,
 };
 use platform::platform::{Cursor, PlatformSpecificWindowBuilderAttributes, WindowId};
@@ -111,6 +116,20 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn focus(&self) {
+        let window = self.window.clone();
+        unsafe {
+            if winuser::IsIconic(window.0) != 0 {
+                // `ShowWindow` resizes the window, so it must be called from the main thread.
+                self.events_loop_proxy.execute_in_thread(move |_| {
+                    winuser::ShowWindow(window.0, winuser::SW_RESTORE);
+                });
+            }
+            force_window_active(self.window.0);
+        }
+    }
+
     pub(crate) fn get_position_physical(&self) -> Option<(i32, i32)> {
         util::get_window_rect(self.window.0)
             .map(|rect| (rect.left as i32, rect.top as i32))
@@ -375,6 +394,101 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn grab_keyboard(&self, _grab: bool) -> Result<(), String> {
+        Err("Keyboard grabbing is not yet possible on Windows.".to_owned())
+    }
+
+    fn drag_initiate(&self, hit_test: isize) -> Result<(), String> {
+        let cursor_pos = util::get_cursor_pos()
+            .ok_or_else(|| "`GetCursorPos` failed".to_owned())?;
+        // The coordinates are packed into the low and high words, just like `MAKELPARAM` does.
+        let position = (cursor_pos.x as u16 as u32 | (cursor_pos.y as u16 as u32) << 16) as LPARAM;
+        unsafe {
+            // Windows takes over the mouse from here, as if the non-client area had been clicked.
+            winuser::ReleaseCapture();
+            winuser::PostMessageW(
+                self.window.0,
+                winuser::WM_NCLBUTTONDOWN,
+                hit_test as WPARAM,
+                position,
+            );
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), String> {
+        self.drag_initiate(winuser::HTCAPTION)
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), String> {
+        self.drag_initiate(match direction {
+            ResizeDirection::East => winuser::HTRIGHT,
+            ResizeDirection::North => winuser::HTTOP,
+            ResizeDirection::NorthEast => winuser::HTTOPRIGHT,
+            ResizeDirection::NorthWest => winuser::HTTOPLEFT,
+            ResizeDirection::South => winuser::HTBOTTOM,
+            ResizeDirection::SouthEast => winuser::HTBOTTOMRIGHT,
+            ResizeDirection::SouthWest => winuser::HTBOTTOMLEFT,
+            ResizeDirection::West => winuser::HTLEFT,
+        })
+    }
+
+    #[inline]
+    pub fn set_hit_test(&self, hit_test: Option<Box<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>) {
+        self.window_state.lock().unwrap().hit_test = hit_test.map(Arc::from);
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, logical_position: LogicalPosition) -> Result<(), String> {
+        let dpi_factor = self.get_hidpi_factor();
+        let (x, y) = logical_position.to_physical(dpi_factor).into();
+        let mut point = POINT { x, y };
+        if unsafe { winuser::ClientToScreen(self.window.0, &mut point) } == 0 {
+            return Err("`ClientToScreen` failed".to_owned());
+        }
+        let window = self.window.clone();
+        unsafe {
+            // The menu runs a modal loop, so it must be shown from the thread owning the window.
+            self.events_loop_proxy.execute_in_thread(move |_| {
+                let menu = winuser::GetSystemMenu(window.0, FALSE);
+                if menu.is_null() {
+                    return;
+                }
+                let command = winuser::TrackPopupMenu(
+                    menu,
+                    winuser::TPM_RETURNCMD | winuser::TPM_RIGHTBUTTON,
+                    point.x,
+                    point.y,
+                    0,
+                    window.0,
+                    ptr::null(),
+                );
+                if command != 0 {
+                    winuser::PostMessageW(window.0, winuser::WM_SYSCOMMAND, command as WPARAM, 0);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_input_region(&self, _region: Option<Vec<Rect>>) -> Result<(), String> {
+        Err("Changing the input region is not yet possible on Windows.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_fullscreen_exclusive(&self, _monitor: RootMonitorId, _video_mode: VideoMode) -> Result<(), String> {
+        Err("Changing the video mode is not yet possible on Windows.".to_owned())
+    }
+
+    #[inline]
+    pub fn set_fullscreen_monitors(&self, _monitors: &[RootMonitorId]) -> Result<(), String> {
+        Err("Spanning fullscreen across monitors is not yet possible on Windows.".to_owned())
+    }
+
     #[inline]
     pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
         let currently_grabbed = unsafe { self.cursor_is_grabbed() }?;
@@ -450,6 +564,31 @@ impl Window {
         WindowId(self.window.0)
     }
 
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        unsafe { winuser::IsZoomed(self.window.0) != 0 }
+    }
+
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        unsafe { winuser::IsIconic(self.window.0) != 0 }
+    }
+
+    #[inline]
+    pub fn is_fullscreen(&self) -> bool {
+        self.window_state.lock().unwrap().fullscreen.is_some()
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        unsafe { winuser::GetForegroundWindow() == self.window.0 }
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        unsafe { winuser::IsWindowVisible(self.window.0) != 0 }
+    }
+
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         let mut window_state = self.window_state.lock().unwrap();
@@ -475,6 +614,24 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        let window = self.window.clone();
+        unsafe {
+            // `ShowWindow` resizes the window, so it must be called from the main thread.
+            self.events_loop_proxy.execute_in_thread(move |_| {
+                winuser::ShowWindow(
+                    window.0,
+                    if minimized {
+                        winuser::SW_MINIMIZE
+                    } else {
+                        winuser::SW_RESTORE
+                    },
+                );
+            });
+        }
+    }
+
     unsafe fn set_fullscreen_style(&self, window_state: &mut WindowState) -> (LONG, LONG) {
         if window_state.fullscreen.is_none() || window_state.saved_window_info.is_none() {
             let rect = util::get_window_rect(self.window.0).expect("`GetWindowRect` failed");
@@ -725,6 +882,52 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        let alpha = (opacity.max(0.0).min(1.0) * 255.0).round() as BYTE;
+        let window = self.window.clone();
+        self.events_loop_proxy.execute_in_thread(move |_| {
+            unsafe {
+                // Only layered windows can be translucent, but they're slower to draw, so the style
+                // is removed again once the window is opaque.
+                let ex_style = winuser::GetWindowLongW(window.0, winuser::GWL_EXSTYLE);
+                if alpha < 255 {
+                    winuser::SetWindowLongW(
+                        window.0,
+                        winuser::GWL_EXSTYLE,
+                        ex_style | winuser::WS_EX_LAYERED as LONG,
+                    );
+                    winuser::SetLayeredWindowAttributes(window.0, 0, alpha, winuser::LWA_ALPHA);
+                } else {
+                    winuser::SetWindowLongW(
+                        window.0,
+                        winuser::GWL_EXSTYLE,
+                        ex_style & !(winuser::WS_EX_LAYERED as LONG),
+                    );
+                }
+            }
+        });
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        let (flags, count) = match request_type {
+            Some(UserAttentionType::Critical) => (winuser::FLASHW_ALL | winuser::FLASHW_TIMERNOFG, UINT::max_value()),
+            Some(UserAttentionType::Informational) => (winuser::FLASHW_TRAY | winuser::FLASHW_TIMERNOFG, 0),
+            None => (winuser::FLASHW_STOP, 0),
+        };
+        let mut flash_info = winuser::FLASHWINFO {
+            cbSize: mem::size_of::<winuser::FLASHWINFO>() as UINT,
+            hwnd: self.window.0,
+            dwFlags: flags,
+            uCount: count,
+            dwTimeout: 0,
+        };
+        unsafe {
+            winuser::FlashWindowEx(&mut flash_info);
+        }
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         RootMonitorId {
@@ -1011,6 +1214,7 @@ unsafe fn init(
             maximized: attributes.maximized,
             resizable: attributes.resizable,
             always_on_top: attributes.always_on_top,
+            hit_test: None,
         };
         // Creating a mutex to track the current window state
         Arc::new(Mutex::new(window_state))
@@ -1035,6 +1239,12 @@ unsafe fn init(
     };
 
     win.set_maximized(attributes.maximized);
+    if attributes.minimized {
+        win.set_minimized(true);
+    }
+    if attributes.opacity < 1.0 {
+        win.set_opacity(attributes.opacity);
+    }
     if let Some(_) = attributes.fullscreen {
         win.set_fullscreen(attributes.fullscreen);
         force_window_active(win.window.0);
@@ -1150,7 +1360,8 @@ unsafe fn mark_fullscreen(handle: HWND, fullscreen: bool) {
 unsafe fn force_window_active(handle: HWND) {
     // In some situation, calling SetForegroundWindow could not bring up the window,
     // This is a little hack which can "steal" the foreground window permission
-    // We only call this function in the window creation, so it should be fine.
+    // We only call this function in the window creation or when explicitly asked to through
+    // `Window::focus`, so it should be fine.
     // See : https://stackoverflow.com/questions/10740346/setforegroundwindow-only-working-while-visual-studio-is-open
     let alt_sc = winuser::MapVirtualKeyW(winuser::VK_MENU as _, winuser::MAPVK_VK_TO_VSC);
 
@@ -84,6 +84,17 @@ impl WindowBuilder {
         self
     }
 
+    /// Requests minimized mode.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This has no effect on iOS, Android or Emscripten.
+    #[inline]
+    pub fn with_minimized(mut self, minimized: bool) -> WindowBuilder {
+        self.window.minimized = minimized;
+        self
+    }
+
     /// Sets whether the window will be initially hidden or visible.
     #[inline]
     pub fn with_visibility(mut self, visible: bool) -> WindowBuilder {
@@ -112,6 +123,15 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets the opacity of the whole window, including its decorations.
+    ///
+    /// See `Window::set_opacity` for details.
+    #[inline]
+    pub fn with_opacity(mut self, opacity: f32) -> WindowBuilder {
+        self.window.opacity = opacity;
+        self
+    }
+
     /// Sets the window icon. On Windows and X11, this is typically the small icon in the top-left
     /// corner of the titlebar.
     ///
@@ -205,6 +225,20 @@ impl Window {
         self.window.hide()
     }
 
+    /// Brings the window to the front and gives it input focus, showing it first if it's hidden
+    /// or minimized.
+    ///
+    /// Window managers may refuse to do this if the user is busy with another application, in
+    /// which case the window usually requests the user's attention instead.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This has no effect on Wayland, iOS, Android or Emscripten.
+    #[inline]
+    pub fn focus(&self) {
+        self.window.focus()
+    }
+
     /// Returns the position of the top-left hand corner of the window relative to the
     ///  top-left hand corner of the desktop.
     ///
@@ -342,6 +376,116 @@ impl Window {
         self.window.grab_cursor(grab)
     }
 
+    /// Grabs the keyboard, so that the window receives all key presses, including shortcuts that would otherwise be
+    /// handled by the window manager, such as Alt+Tab.
+    ///
+    /// This is meant for applications such as virtual machine viewers and remote desktop clients. Remember to
+    /// release the grab, since other applications can't receive keyboard input in the meantime.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The window must be visible.
+    /// - **Wayland:** Requires a compositor supporting the `keyboard-shortcuts-inhibit` protocol, which may ask the
+    ///   user for permission first. The grab only applies while the window has keyboard focus.
+    /// - **Windows, macOS, iOS, Android and Emscripten:** Always returns an error.
+    #[inline]
+    pub fn grab_keyboard(&self, grab: bool) -> Result<(), String> {
+        self.window.grab_keyboard(grab)
+    }
+
+    /// Lets the user move the window with the mouse, as if they had started dragging its title bar.
+    ///
+    /// This should be called right after receiving a left mouse button press, and the move lasts
+    /// until the button is released. It's meant for windows that draw their own title bar.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a window manager supporting `_NET_WM_MOVERESIZE`. The window manager has
+    ///   to grab the cursor itself, so this releases any grab made with `grab_cursor`. Call
+    ///   `grab_cursor(true)` again once the button is released to get it back.
+    /// - **Wayland, iOS, Android and Emscripten:** Always returns an error.
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), String> {
+        self.window.drag_window()
+    }
+
+    /// Lets the user resize the window with the mouse, as if they had started dragging the given
+    /// edge or corner of its frame.
+    ///
+    /// This should be called right after receiving a left mouse button press, and the resize lasts
+    /// until the button is released.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a window manager supporting `_NET_WM_MOVERESIZE`. The window manager has
+    ///   to grab the cursor itself, so this releases any grab made with `grab_cursor`. Call
+    ///   `grab_cursor(true)` again once the button is released to get it back.
+    /// - **Wayland, macOS, iOS, Android and Emscripten:** Always returns an error.
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), String> {
+        self.window.drag_resize_window(direction)
+    }
+
+    /// Sets a function that tells which part of the window a point belongs to, so that pressing
+    /// the left mouse button there moves or resizes the window, or acts as one of its buttons, the
+    /// way the window's decorations would. `None` removes it.
+    ///
+    /// The point is given relative to the top-left corner of the window. Presses on the parts
+    /// that aren't `HitTestResult::Client` aren't delivered as `WindowEvent::MouseInput`, and a
+    /// press on `HitTestResult::Close` is delivered as `WindowEvent::CloseRequested` instead.
+    ///
+    /// This is meant for windows created with `with_decorations(false)` that draw their own title
+    /// bar and borders.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Moving and resizing require a window manager supporting `_NET_WM_MOVERESIZE`.
+    /// - **Wayland, macOS, iOS, Android and Emscripten:** Has no effect.
+    #[inline]
+    pub fn set_hit_test(&self, hit_test: Option<Box<Fn(LogicalPosition) -> HitTestResult + Send + Sync>>) {
+        self.window.set_hit_test(hit_test)
+    }
+
+    /// Shows the menu the window manager would show when right-clicking the window's title bar,
+    /// at `position` relative to the top-left corner of the window.
+    ///
+    /// This is meant for windows that draw their own title bar, and should be called in response
+    /// to a mouse button press.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a window manager supporting `_GTK_SHOW_WINDOW_MENU`, like Mutter or KWin.
+    /// - **Wayland, macOS, iOS, Android and Emscripten:** Always returns an error.
+    #[inline]
+    pub fn show_window_menu(&self, position: LogicalPosition) -> Result<(), String> {
+        self.window.show_window_menu(position)
+    }
+
+    /// Restricts the parts of the window that receive mouse input to `region`. Mouse input
+    /// anywhere else goes to whatever is underneath the window. `None` makes the whole window
+    /// receive input again.
+    ///
+    /// The rectangles are given relative to the top-left corner of the window, and an empty
+    /// region makes the entire window click-through.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires the XFixes extension.
+    /// - **Wayland:** The region takes effect with the next frame drawn to the window.
+    /// - **Windows, macOS, iOS, Android and Emscripten:** Always returns an error.
+    #[inline]
+    pub fn set_input_region(&self, region: Option<Vec<Rect>>) -> Result<(), String> {
+        self.window.set_input_region(region)
+    }
+
+    /// Changes whether the window receives mouse input at all. This is a shortcut for
+    /// `set_input_region(None)` when `hittest` is true and `set_input_region(Some(vec![]))`
+    /// otherwise.
+    #[inline]
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), String> {
+        self.set_input_region(if hittest { None } else { Some(Vec::new()) })
+    }
+
     /// Hides the cursor, making it invisible but still usable.
     ///
     /// ## Platform-specific
@@ -357,18 +501,110 @@ impl Window {
         self.window.hide_cursor(hide)
     }
 
+    /// Returns whether the window is currently maximized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11, Wayland and Windows. Other platforms always return `false`.
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        self.window.is_maximized()
+    }
+
+    /// Returns whether the window is currently minimized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11 and Windows. Wayland doesn't tell clients when they're
+    /// minimized, so it and the other platforms always return `false`.
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        self.window.is_minimized()
+    }
+
+    /// Returns whether the window is currently fullscreen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11, Wayland and Windows. Other platforms always return `false`.
+    #[inline]
+    pub fn is_fullscreen(&self) -> bool {
+        self.window.is_fullscreen()
+    }
+
+    /// Returns whether the window currently has input focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11, Wayland and Windows. Other platforms always return `false`.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.window.is_focused()
+    }
+
+    /// Returns whether the window is currently shown, i.e. it hasn't been hidden with `hide`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11, Wayland and Windows. Other platforms always return `false`.
+    /// On X11, most window managers also hide minimized windows.
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        self.window.is_visible()
+    }
+
     /// Sets the window to maximized or back
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         self.window.set_maximized(maximized)
     }
 
+    /// Sets the window to minimized or back.
+    ///
+    /// ## Platform-specific
+    ///
+    /// On Wayland, windows can only be minimized; `set_minimized(false)` has no effect. This has no
+    /// effect on iOS, Android or Emscripten.
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        self.window.set_minimized(minimized)
+    }
+
     /// Sets the window to fullscreen or back
     #[inline]
     pub fn set_fullscreen(&self, monitor: Option<MonitorId>) {
         self.window.set_fullscreen(monitor)
     }
 
+    /// Sets the window to fullscreen on `monitor`, switching the monitor to `video_mode`.
+    ///
+    /// `video_mode` must be one of the modes returned by `monitor.get_video_modes()`. The
+    /// monitor's previous mode is restored when the window leaves fullscreen through
+    /// `set_fullscreen`, or when the window is dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11. Other platforms always return an error.
+    #[inline]
+    pub fn set_fullscreen_exclusive(&self, monitor: MonitorId, video_mode: VideoMode) -> Result<(), String> {
+        self.window.set_fullscreen_exclusive(monitor, video_mode)
+    }
+
+    /// Sets the window to a single fullscreen surface spanning all of `monitors`.
+    ///
+    /// The window covers the bounding box of the monitors, so they should form a rectangle.
+    /// Passing an empty slice is the same as calling `set_fullscreen(None)`. Calling
+    /// `set_fullscreen` afterwards makes the window cover a single monitor again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11, and requires a window manager supporting
+    /// `_NET_WM_FULLSCREEN_MONITORS`. Other platforms always return an error.
+    #[inline]
+    pub fn set_fullscreen_monitors(&self, monitors: &[MonitorId]) -> Result<(), String> {
+        self.window.set_fullscreen_monitors(monitors)
+    }
+
     /// Turn window decorations on or off.
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
@@ -381,6 +617,35 @@ impl Window {
         self.window.set_always_on_top(always_on_top)
     }
 
+    /// Sets the opacity of the whole window, including its decorations, from `0.0` (invisible) to
+    /// `1.0` (opaque). Values outside of that range are clamped.
+    ///
+    /// Unlike `with_transparency`, this applies uniformly to the entire window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sets `_NET_WM_WINDOW_OPACITY`, which needs a running compositor to take effect.
+    /// - **Wayland, iOS, Android and Emscripten:** Has no effect.
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        self.window.set_opacity(opacity)
+    }
+
+    /// Requests that the user pays attention to the window, e.g. because something happened in it
+    /// while it wasn't focused. `None` withdraws an earlier request.
+    ///
+    /// The request is withdrawn automatically once the window is focused.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sets the urgency hint; both types of request are treated the same.
+    /// - **macOS:** `None` has no effect.
+    /// - **Wayland, iOS, Android and Emscripten:** Unsupported.
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        self.window.request_user_attention(request_type)
+    }
+
     /// Sets the window icon. On Windows and X11, this is typically the small icon in the top-left
     /// corner of the titlebar.
     ///
@@ -491,4 +756,315 @@ impl MonitorId {
     pub fn get_hidpi_factor(&self) -> f64 {
         self.inner.get_hidpi_factor()
     }
+
+    /// Returns the monitor's refresh rate, in Hz.
+    ///
+    /// Returns `None` if the refresh rate is unknown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        self.inner.get_refresh_rate()
+    }
+
+    /// Returns the physical dimensions of the monitor's panel, in millimetres.
+    ///
+    /// These are the dimensions of the unrotated panel, so they don't account for the
+    /// monitor's transform. Returns `None` if the dimensions are unknown, as is the case for
+    /// projectors.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_physical_dimensions(&self) -> Option<(u32, u32)> {
+        self.inner.get_physical_dimensions()
+    }
+
+    /// Returns how the contents of the monitor are rotated and flipped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns `MonitorTransform::Normal`.
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        self.inner.get_transform()
+    }
+
+    /// Returns the arrangement of the subpixels of the monitor's panel.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns `SubpixelLayout::Unknown`.
+    #[inline]
+    pub fn get_subpixel_layout(&self) -> SubpixelLayout {
+        self.inner.get_subpixel_layout()
+    }
+
+    /// Returns the name of the monitor's manufacturer.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The three letter PNP ID from the monitor's EDID, e.g. "DEL".
+    /// - **Wayland:** The make reported by the compositor.
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        self.inner.get_manufacturer()
+    }
+
+    /// Returns the monitor's model name.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The name from the monitor's EDID, or its product code in hexadecimal if it has
+    ///   no name.
+    /// - **Wayland:** The model reported by the compositor.
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        self.inner.get_model()
+    }
+
+    /// Returns the monitor's serial number.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Read from the monitor's EDID.
+    /// - **Wayland / Windows / macOS / iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_serial_number(&self) -> Option<String> {
+        self.inner.get_serial_number()
+    }
+
+    /// Returns an identifier for the monitor that stays the same across reboots, and regardless
+    /// of which connector the monitor is plugged into.
+    ///
+    /// Unlike `get_name`, this is suitable for remembering which monitor a window was on.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Built from the manufacturer, product code and serial number in the monitor's
+    ///   EDID. Returns `None` if the monitor has no EDID.
+    /// - **Wayland:** Built from the make and model, so identical monitors share the same
+    ///   identifier.
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_persistent_id(&self) -> Option<String> {
+        self.inner.get_persistent_id()
+    }
+
+    /// Returns the position and size of the part of the monitor that isn't covered by panels,
+    /// docks or taskbars, in the same coordinates as `get_position`.
+    ///
+    /// This is where new windows and popups should be placed so that nothing hides them.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Based on the struts reserved by panels through `_NET_WM_STRUT_PARTIAL`, or on
+    ///   `_NET_WORKAREA` if the window manager doesn't list the windows it manages.
+    /// - **Wayland / macOS / iOS / Android / Emscripten:** Returns the whole monitor.
+    #[inline]
+    pub fn get_work_area(&self) -> (PhysicalPosition, PhysicalSize) {
+        self.inner.get_work_area()
+    }
+
+    /// Returns the monitor's current gamma ramp.
+    ///
+    /// Returns `None` if the gamma ramp can't be read.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / Windows / macOS / iOS / Android / Emscripten:** Always returns `None`.
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        self.inner.get_gamma_ramp()
+    }
+
+    /// Sets the monitor's gamma ramp.
+    ///
+    /// Each channel of `ramp` must have as many entries as those returned by `get_gamma_ramp`.
+    /// The gamma ramps of all the monitors changed this way are restored when the `EventsLoop` is
+    /// dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11, through XRandR. Other platforms always return an error.
+    #[inline]
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), String> {
+        self.inner.set_gamma_ramp(ramp)
+    }
+
+    /// Returns the video modes the monitor can be switched to.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The modes are reported by the compositor, but can't be switched to.
+    /// - **Windows / macOS / iOS / Android / Emscripten:** Always returns an empty list.
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        self.inner.get_video_modes()
+    }
+}
+
+/// The curves mapping the values of each color channel to the intensity the monitor displays,
+/// as used by `MonitorId::get_gamma_ramp` and `MonitorId::set_gamma_ramp`.
+///
+/// Each channel maps evenly spaced input values, from darkest to brightest, to output values
+/// ranging from 0 to 65535.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GammaRamp {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+}
+
+/// The edge or corner of a window to resize from with `Window::drag_resize_window`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ResizeDirection {
+    East,
+    North,
+    NorthEast,
+    NorthWest,
+    South,
+    SouthEast,
+    SouthWest,
+    West,
+}
+
+/// A rectangle in logical pixels, relative to the top-left corner of a window.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rect {
+    pub position: LogicalPosition,
+    pub size: LogicalSize,
+}
+
+/// The part of a window a point belongs to, as returned by the function given to
+/// `Window::set_hit_test`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HitTestResult {
+    /// The point is handled by the application as usual.
+    Client,
+    /// Pressing here moves the window, like its title bar would.
+    Caption,
+    /// Pressing here resizes the window from the given edge or corner.
+    Resize(ResizeDirection),
+    /// Pressing here minimizes the window.
+    Minimize,
+    /// Pressing here maximizes the window, or restores it if it's already maximized.
+    Maximize,
+    /// Pressing here asks for the window to be closed.
+    Close,
+}
+
+/// How insistently `Window::request_user_attention` should get the user's attention.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UserAttentionType {
+    /// Keeps getting the user's attention until the window is focused, e.g. by bouncing the dock
+    /// icon on macOS or flashing the taskbar entry on Windows.
+    Critical,
+    /// Gets the user's attention once, or in the least intrusive way the platform offers.
+    Informational,
+}
+
+impl Default for UserAttentionType {
+    #[inline]
+    fn default() -> Self {
+        UserAttentionType::Informational
+    }
+}
+
+/// A snapshot of the state of a window, as reported by `WindowEvent::StateChanged`.
+///
+/// See `Window::is_maximized` and the related methods for what each field means.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WindowState {
+    pub maximized: bool,
+    pub minimized: bool,
+    pub fullscreen: bool,
+    pub focused: bool,
+    pub visible: bool,
+}
+
+/// A resolution, refresh rate and color depth that a monitor supports.
+///
+/// Obtained through `MonitorId::get_video_modes`, and used with `Window::set_fullscreen_exclusive`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    pub(crate) dimensions: (u32, u32),
+    pub(crate) refresh_rate: u16,
+    pub(crate) bit_depth: u16,
+}
+
+impl VideoMode {
+    /// Returns the resolution of this video mode.
+    #[inline]
+    pub fn get_dimensions(&self) -> PhysicalSize {
+        self.dimensions.into()
+    }
+
+    /// Returns the refresh rate of this video mode, in Hz.
+    ///
+    /// Returns 0 if the refresh rate is unknown.
+    #[inline]
+    pub fn get_refresh_rate(&self) -> u16 {
+        self.refresh_rate
+    }
+
+    /// Returns the number of bits per pixel of this video mode.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The compositor doesn't report the color depth, so this is always 24.
+    #[inline]
+    pub fn get_bit_depth(&self) -> u16 {
+        self.bit_depth
+    }
+}
+
+/// How the contents of a monitor are rotated and flipped, as returned by
+/// `MonitorId::get_transform`.
+///
+/// Rotations are counter-clockwise. The flipped variants are mirrored around the vertical axis
+/// before being rotated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MonitorTransform {
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl Default for MonitorTransform {
+    fn default() -> Self {
+        MonitorTransform::Normal
+    }
+}
+
+/// The arrangement of the subpixels of a monitor's panel, as returned by
+/// `MonitorId::get_subpixel_layout`.
+///
+/// This is the layout of the unrotated panel, and is mostly of use for subpixel text
+/// antialiasing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SubpixelLayout {
+    Unknown,
+    /// The pixels aren't made of subpixels, as is the case for projectors.
+    None,
+    HorizontalRgb,
+    HorizontalBgr,
+    VerticalRgb,
+    VerticalBgr,
+}
+
+impl Default for SubpixelLayout {
+    fn default() -> Self {
+        SubpixelLayout::Unknown
+    }
 }